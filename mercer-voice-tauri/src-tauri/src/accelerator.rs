@@ -0,0 +1,91 @@
+//! Parses a human-written hotkey accelerator like `"Cmd+Shift+V"` or `"Ctrl+Alt+Space"` into a
+//! structured `Accelerator { mods, key }` so the dictation/paste chord can be user-configurable
+//! instead of hard-coded to the Fn/Globe key.
+
+/// Modifier bitmask. Values are private flags; combine with `|`.
+pub mod modifiers {
+    pub const SHIFT: u8 = 1 << 0;
+    pub const CONTROL: u8 = 1 << 1;
+    pub const ALT: u8 = 1 << 2;
+    pub const META: u8 = 1 << 3;
+}
+
+/// A parsed key binding: a set of modifier flags plus the final key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accelerator {
+    pub mods: u8,
+    pub key: KeyCode,
+}
+
+/// The non-modifier key an accelerator ends in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyCode {
+    /// A letter or digit (stored uppercase for letters).
+    Char(char),
+    /// A punctuation key spelled out in the accelerator (`,` `-` `.` `=` `;` `/` `\` `'` `` ` `` `[` `]`).
+    Punct(char),
+    Space,
+    Tab,
+    /// Function keys F1–F24.
+    Function(u8),
+}
+
+impl Accelerator {
+    /// Parse an accelerator string. Tokens are split on `+`; all but the last must be modifiers.
+    pub fn parse(s: &str) -> Result<Accelerator, String> {
+        let trimmed = s.trim();
+        if trimmed.is_empty() {
+            return Err("Empty accelerator".to_string());
+        }
+        let tokens: Vec<&str> = trimmed.split('+').map(str::trim).filter(|t| !t.is_empty()).collect();
+        if tokens.is_empty() {
+            return Err(format!("Invalid accelerator: {}", s));
+        }
+
+        let mut mods = 0u8;
+        for tok in &tokens[..tokens.len() - 1] {
+            mods |= parse_modifier(tok)
+                .ok_or_else(|| format!("Unknown modifier: {}", tok))?;
+        }
+        let key = parse_key(tokens[tokens.len() - 1])?;
+        Ok(Accelerator { mods, key })
+    }
+}
+
+fn parse_modifier(tok: &str) -> Option<u8> {
+    match tok.to_ascii_lowercase().as_str() {
+        "cmd" | "command" | "meta" | "super" | "win" => Some(modifiers::META),
+        "ctrl" | "control" => Some(modifiers::CONTROL),
+        "alt" | "option" | "opt" => Some(modifiers::ALT),
+        "shift" => Some(modifiers::SHIFT),
+        _ => None,
+    }
+}
+
+fn parse_key(tok: &str) -> Result<KeyCode, String> {
+    let lower = tok.to_ascii_lowercase();
+    match lower.as_str() {
+        "space" => return Ok(KeyCode::Space),
+        "tab" => return Ok(KeyCode::Tab),
+        _ => {}
+    }
+    // Function keys F1–F24.
+    if let Some(rest) = lower.strip_prefix('f') {
+        if let Ok(n) = rest.parse::<u8>() {
+            if (1..=24).contains(&n) {
+                return Ok(KeyCode::Function(n));
+            }
+        }
+    }
+    // Single-character keys: letters/digits and the supported punctuation.
+    let mut chars = tok.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_alphanumeric() {
+            return Ok(KeyCode::Char(c.to_ascii_uppercase()));
+        }
+        if matches!(c, ',' | '-' | '.' | '=' | ';' | '/' | '\\' | '\'' | '`' | '[' | ']') {
+            return Ok(KeyCode::Punct(c));
+        }
+    }
+    Err(format!("Unknown key: {}", tok))
+}
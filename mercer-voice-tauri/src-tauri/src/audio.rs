@@ -1,13 +1,76 @@
 use crate::store::Store;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use hound::{WavSpec, WavWriter};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Emitter, Manager, State};
 
 enum AudioCommand {
     Stop,
+    /// Emit a streaming chunk now, regardless of the timer (e.g. when the transcriber is idle).
+    Flush,
+}
+
+/// Rolling window flushed to the frontend in streaming mode.
+const STREAM_FLUSH_MS: u64 = 2500;
+/// Overlap carried into the next window so words straddling a boundary aren't cut. A backend
+/// consumer that concatenates windows must drop this leading overlap on every window after the first.
+pub const STREAM_OVERLAP_MS: u64 = 500;
+
+/// Auto-stop fires after this much continuous trailing silence.
+const AUTO_STOP_SILENCE_MS: f64 = 1500.0;
+/// A chunk counts as silence when its RMS stays below `noise_floor * this`.
+const SILENCE_NOISE_FACTOR: f64 = 2.5;
+/// Padding kept on either side of the trimmed speech region so words aren't clipped.
+const TRIM_PADDING_MS: u32 = 150;
+
+/// Running silence estimate fed by the capture callback's per-chunk RMS. The worker loop reads it
+/// to decide when hands-free auto-stop should fire.
+#[derive(Default)]
+struct SilenceTracker {
+    /// Adaptive noise floor: tracks the quietest level seen, drifting slowly upward.
+    noise_floor: f64,
+    /// Whether anything above the speech threshold has been heard yet.
+    speech_seen: bool,
+    /// Accumulated trailing silence in milliseconds since the last speech chunk.
+    silent_ms: f64,
+}
+
+impl SilenceTracker {
+    /// Fold in one chunk's RMS, advancing the trailing-silence estimate.
+    fn push(&mut self, rms: f64, chunk_ms: f64) {
+        if self.noise_floor == 0.0 {
+            self.noise_floor = rms.max(1.0);
+        } else if rms < self.noise_floor {
+            self.noise_floor = rms;
+        } else {
+            // Slow upward drift so a changing room tone is still tracked.
+            self.noise_floor = self.noise_floor * 0.999 + rms * 0.001;
+        }
+        if rms > self.noise_floor * SILENCE_NOISE_FACTOR {
+            self.speech_seen = true;
+            self.silent_ms = 0.0;
+        } else {
+            self.silent_ms += chunk_ms;
+        }
+    }
+
+    /// True once speech has been heard and the trailing silence has run long enough to auto-stop.
+    fn should_auto_stop(&self) -> bool {
+        self.speech_seen && self.silent_ms >= AUTO_STOP_SILENCE_MS
+    }
+}
+
+/// What the capture stream listens to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureSource {
+    /// An input device (the default).
+    #[default]
+    Microphone,
+    /// System output, captured via the platform loopback endpoint (meeting/video audio).
+    Loopback,
 }
 
 pub struct AudioState {
@@ -15,6 +78,12 @@ pub struct AudioState {
     stop_tx: Mutex<Option<mpsc::Sender<AudioCommand>>>,
     /// Sample rate of the last recording (set when stream starts).
     sample_rate: Mutex<u32>,
+    /// Preferred input device name from the picker. `None` (or a now-absent device) = system default.
+    selected_device: Mutex<Option<String>>,
+    /// Microphone vs. system-audio loopback.
+    source: Mutex<CaptureSource>,
+    /// Emit overlapping `audio-chunk` windows during capture for incremental transcription.
+    streaming: Mutex<bool>,
 }
 
 impl Default for AudioState {
@@ -23,14 +92,56 @@ impl Default for AudioState {
             samples: Arc::new(Mutex::new(Vec::new())),
             stop_tx: Mutex::new(None),
             sample_rate: Mutex::new(16000),
+            selected_device: Mutex::new(None),
+            source: Mutex::new(CaptureSource::Microphone),
+            streaming: Mutex::new(false),
         }
     }
 }
 
+/// An available input device and its default capture format, for a frontend picker.
+#[derive(Debug, Clone, Serialize)]
+pub struct InputDeviceInfo {
+    pub name: String,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
 fn emit_recording_failed(app: &AppHandle, msg: &str) {
     let _ = app.emit_to("main", "recording-failed", msg);
 }
 
+/// Why a recording ended, so the frontend can tell a hotkey release from hands-free auto-stop.
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum StopReason {
+    Manual,
+    Auto,
+}
+
+#[derive(Clone, Serialize)]
+struct StoppedPayload {
+    reason: StopReason,
+}
+
+fn emit_recording_stopped(app: &AppHandle, reason: StopReason) {
+    let _ = app.emit_to("main", "recording-stopped", StoppedPayload { reason });
+}
+
+/// One streaming window (mono, 16 kHz) handed to the transcription layer mid-capture. `is_final`
+/// marks the tail emitted on stop. Also deserialized by the backend streaming consumer, which reads
+/// the same `audio-chunk` events the overlay does.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AudioChunk {
+    pub samples: Vec<i16>,
+    pub sample_rate: u32,
+    pub is_final: bool,
+}
+
+fn emit_audio_chunk(app: &AppHandle, chunk: AudioChunk) {
+    let _ = app.emit_to("main", "audio-chunk", chunk);
+}
+
 /// Called from both the Tauri command (frontend) and the hotkey handler (backend-only flow).
 pub fn start_recording_impl(app: &AppHandle) -> Result<(), String> {
     let store = app.state::<Store>();
@@ -45,24 +156,51 @@ fn do_start_recording(app: AppHandle, state: State<'_, AudioState>) -> Result<()
     eprintln!("[Verba] start_recording");
 
     let host = cpal::default_host();
-    let device = host.default_input_device().ok_or_else(|| {
-        if cfg!(target_os = "windows") {
-            "No microphone found. Open Windows Settings → Privacy & Security → Microphone and make sure microphone access is turned ON, then restart Verba.".to_string()
-        } else {
-            "No microphone found. On macOS, grant access in System Settings → Privacy & Security → Microphone for Verba.".to_string()
+    let source = *state.source.lock().unwrap();
+
+    // Core Audio has no native loopback; point users at an aggregate device instead of failing deep
+    // in the stream builder.
+    if source == CaptureSource::Loopback && cfg!(target_os = "macos") {
+        return Err("System-audio capture isn't available natively on macOS. Install an aggregate/loopback device such as BlackHole or Loopback, select it as your input device, then record in microphone mode.".to_string());
+    }
+
+    // Microphone uses the picker's device (falling back to the default); loopback taps the default
+    // output endpoint (WASAPI loopback on Windows). Both hand us a `SupportedStreamConfig`.
+    let (device, default_supported) = match source {
+        CaptureSource::Microphone => {
+            let selected = state.selected_device.lock().unwrap().clone();
+            let device = select_input_device(&host, selected.as_deref()).ok_or_else(|| {
+                if cfg!(target_os = "windows") {
+                    "No microphone found. Open Windows Settings → Privacy & Security → Microphone and make sure microphone access is turned ON, then restart Verba.".to_string()
+                } else {
+                    "No microphone found. On macOS, grant access in System Settings → Privacy & Security → Microphone for Verba.".to_string()
+                }
+            })?;
+            let config = device
+                .default_input_config()
+                .map_err(|e| format!("Failed to get default input config: {}", e))?;
+            (device, config)
+        }
+        CaptureSource::Loopback => {
+            let device = host
+                .default_output_device()
+                .ok_or_else(|| "No system-audio output device found for loopback capture.".to_string())?;
+            let config = device
+                .default_output_config()
+                .map_err(|e| format!("Failed to get default output config: {}", e))?;
+            (device, config)
         }
-    })?;
+    };
 
     eprintln!(
-        "[Verba] Using input device: {}",
+        "[Verba] Using {} device: {}",
+        match source {
+            CaptureSource::Microphone => "input",
+            CaptureSource::Loopback => "loopback",
+        },
         device.name().unwrap_or_default()
     );
 
-    // Use the device's default config so we match its native format (e.g. Float32 on macOS).
-    let default_supported = device
-        .default_input_config()
-        .map_err(|e| format!("Failed to get default input config: {}", e))?;
-
     let config: cpal::StreamConfig = default_supported.into();
     let target_sample_rate = config.sample_rate.0;
     let target_channels = config.channels;
@@ -82,6 +220,13 @@ fn do_start_recording(app: AppHandle, state: State<'_, AudioState>) -> Result<()
     *state.stop_tx.lock().unwrap() = Some(stop_tx);
 
     let channels = target_channels;
+    // Hands-free auto-stop: off unless the user opted in.
+    let auto_stop = app.state::<Store>().is_auto_stop_enabled();
+    let silence = Arc::new(Mutex::new(SilenceTracker::default()));
+    let silence_cb = silence.clone();
+    // Incremental streaming: flush overlapping windows of the captured buffer while recording.
+    let streaming = *state.streaming.lock().unwrap();
+    let samples_for_stream = state.samples.clone();
 
     // Spawn audio capture on a dedicated thread (cpal::Stream is !Send).
     // Use f32 callback: macOS Core Audio typically uses Float32 natively.
@@ -142,6 +287,9 @@ fn do_start_recording(app: AppHandle, state: State<'_, AudioState>) -> Result<()
                 if chunk.len() >= mono_chunk_size {
                     let sum: f64 = chunk.iter().map(|&s| (s as f64).powi(2)).sum();
                     let rms = (sum / chunk.len() as f64).sqrt();
+                    // Feed the same energy signal to the auto-stop tracker.
+                    let chunk_ms = chunk.len() as f64 / target_sample_rate as f64 * 1000.0;
+                    silence_cb.lock().unwrap().push(rms, chunk_ms);
                     // Scale so normal speech gives visible movement (~8x gain, cap at 1)
                     let level = (rms / 32767.0 * 8.0).min(1.0) as f32;
                     #[derive(Clone, Serialize)]
@@ -194,14 +342,138 @@ fn do_start_recording(app: AppHandle, state: State<'_, AudioState>) -> Result<()
         eprintln!("[Verba] Audio stream started");
         let _ = app_handle.emit_to("main", "recording-started", ());
 
-        let _ = stop_rx.recv();
+        // Streaming flush bookkeeping: how many raw samples have been emitted, and the window/overlap
+        // sizes expressed in raw (device-rate) samples.
+        let flush_window = (target_sample_rate as u64 * STREAM_FLUSH_MS / 1000) as usize;
+        let flush_overlap = (target_sample_rate as u64 * STREAM_OVERLAP_MS / 1000) as usize;
+        let mut emitted = 0usize;
+        let mut flush = |is_final: bool| {
+            let captured = samples_for_stream.lock().unwrap();
+            if captured.len() <= emitted && !is_final {
+                return;
+            }
+            let start = emitted.saturating_sub(flush_overlap);
+            let window: Vec<i16> = captured[start..].to_vec();
+            emitted = captured.len();
+            drop(captured);
+            if window.is_empty() {
+                return;
+            }
+            // Resample to Whisper's 16 kHz so the transcription layer can consume chunks directly.
+            const TARGET_RATE: u32 = 16000;
+            let (samples, sample_rate) = if target_sample_rate > TARGET_RATE {
+                (downsample(&window, target_sample_rate, TARGET_RATE), TARGET_RATE)
+            } else {
+                (window, target_sample_rate)
+            };
+            emit_audio_chunk(&app_handle, AudioChunk { samples, sample_rate, is_final });
+        };
+
+        // Wait for an explicit stop, polling the silence tracker for hands-free auto-stop and
+        // flushing streaming windows as the buffer fills.
+        loop {
+            match stop_rx.recv_timeout(std::time::Duration::from_millis(100)) {
+                Ok(AudioCommand::Stop) => break,
+                Ok(AudioCommand::Flush) => {
+                    if streaming {
+                        flush(false);
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    if streaming
+                        && samples_for_stream.lock().unwrap().len().saturating_sub(emitted)
+                            >= flush_window
+                    {
+                        flush(false);
+                    }
+                    if auto_stop && silence.lock().unwrap().should_auto_stop() {
+                        eprintln!("[Verba] Auto-stopping after trailing silence");
+                        emit_recording_stopped(&app_handle, StopReason::Auto);
+                        break;
+                    }
+                }
+            }
+        }
         *recording.lock().unwrap() = false;
+        // Emit the remaining tail so the last partial words reach the transcriber.
+        if streaming {
+            flush(true);
+        }
         eprintln!("[Verba] Audio stream stopped");
     });
 
     Ok(())
 }
 
+/// Resolve the capture device by name, falling back to the system default when `name` is `None`
+/// or no longer present (e.g. the chosen mic was unplugged).
+fn select_input_device(host: &cpal::Host, name: Option<&str>) -> Option<cpal::Device> {
+    if let Some(want) = name {
+        if let Ok(devices) = host.input_devices() {
+            for device in devices {
+                if device.name().map(|n| n == want).unwrap_or(false) {
+                    return Some(device);
+                }
+            }
+        }
+    }
+    host.default_input_device()
+}
+
+/// Enumerate input devices and their default capture format for the frontend picker.
+#[tauri::command]
+pub fn list_input_devices() -> Vec<InputDeviceInfo> {
+    let host = cpal::default_host();
+    let Ok(devices) = host.input_devices() else {
+        return Vec::new();
+    };
+    devices
+        .filter_map(|device| {
+            let name = device.name().ok()?;
+            let config = device.default_input_config().ok()?;
+            Some(InputDeviceInfo {
+                name,
+                sample_rate: config.sample_rate().0,
+                channels: config.channels(),
+            })
+        })
+        .collect()
+}
+
+/// Set the preferred input device by name; `None`/empty restores the system default.
+#[tauri::command]
+pub fn set_input_device(state: State<'_, AudioState>, name: Option<String>) {
+    let name = name.filter(|n| !n.trim().is_empty());
+    *state.selected_device.lock().unwrap() = name;
+}
+
+/// Switch between microphone and system-audio loopback capture. Takes effect on the next recording.
+#[tauri::command]
+pub fn set_capture_source(state: State<'_, AudioState>, source: CaptureSource) {
+    *state.source.lock().unwrap() = source;
+}
+
+/// Enable or disable incremental `audio-chunk` streaming. Takes effect on the next recording.
+#[tauri::command]
+pub fn set_streaming(state: State<'_, AudioState>, enabled: bool) {
+    *state.streaming.lock().unwrap() = enabled;
+}
+
+/// Whether incremental `audio-chunk` streaming is currently enabled.
+pub fn is_streaming(state: &AudioState) -> bool {
+    *state.streaming.lock().unwrap()
+}
+
+/// Force the capture thread to emit a streaming window now, so the transcriber can pull a partial
+/// between the regular flush intervals. No-op when not recording or not in streaming mode.
+#[tauri::command]
+pub fn flush_stream(state: State<'_, AudioState>) {
+    if let Some(tx) = state.stop_tx.lock().unwrap().as_ref() {
+        let _ = tx.send(AudioCommand::Flush);
+    }
+}
+
 #[tauri::command]
 pub fn start_recording(app: AppHandle, state: State<'_, AudioState>) -> Result<(), String> {
     let store = app.state::<Store>();
@@ -214,12 +486,17 @@ pub fn start_recording(app: AppHandle, state: State<'_, AudioState>) -> Result<(
 /// Called from both the Tauri command and the hotkey handler.
 pub fn stop_recording_impl(app: &AppHandle) -> Result<String, String> {
     let state = app.state::<AudioState>();
-    let path = do_stop_recording(state)?;
-    let _ = app.emit_to("main", "recording-stopped", ());
+    let store = app.state::<Store>();
+    let path = do_stop_recording(state, store.is_noise_reduction_enabled(), store.is_trim_silence_enabled())?;
+    emit_recording_stopped(app, StopReason::Manual);
     Ok(path)
 }
 
-fn do_stop_recording(state: State<'_, AudioState>) -> Result<String, String> {
+fn do_stop_recording(
+    state: State<'_, AudioState>,
+    denoise: bool,
+    trim: bool,
+) -> Result<String, String> {
     eprintln!("[Verba] stop_recording");
 
     // Send stop signal to the audio thread
@@ -253,6 +530,20 @@ fn do_stop_recording(state: State<'_, AudioState>) -> Result<String, String> {
         device_rate, wav_rate, samples.len(), wav_samples.len()
     );
 
+    // Drop leading/trailing silence so uploads and transcriptions aren't padded with dead air.
+    let wav_samples = if trim {
+        trim_silence(&wav_samples, wav_rate)
+    } else {
+        wav_samples
+    };
+
+    // Optional spectral-gate noise reduction before export (improves Whisper accuracy in noisy rooms).
+    let wav_samples = if denoise {
+        crate::denoise::spectral_gate(&wav_samples, wav_rate)
+    } else {
+        wav_samples
+    };
+
     // Write WAV to temp file (mono, 16 kHz)
     let temp_path = std::env::temp_dir().join("verba_recording.wav");
     let spec = WavSpec {
@@ -287,31 +578,159 @@ fn do_stop_recording(state: State<'_, AudioState>) -> Result<String, String> {
     Ok(temp_path.to_string_lossy().to_string())
 }
 
-/// Linear-interpolation downsample from `from_rate` to `to_rate`.
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        let t = b;
+        b = a % b;
+        a = t;
+    }
+    a.max(1)
+}
+
+/// Band-limited rational resampler. Linear interpolation folds content above the output Nyquist
+/// back into the speech band; instead we convolve a windowed-sinc anti-aliasing kernel (cutoff at
+/// `0.45 * to_rate`) against the input at the polyphase positions implied by the `up/down` factors
+/// derived from `gcd(from, to)`, normalizing the taps used per output sample for unity DC gain.
 fn downsample(samples: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
     if from_rate == to_rate || samples.is_empty() {
         return samples.to_vec();
     }
-    let ratio = from_rate as f64 / to_rate as f64;
-    let out_len = (samples.len() as f64 / ratio) as usize;
-    let mut out = Vec::with_capacity(out_len);
-    for i in 0..out_len {
-        let src = i as f64 * ratio;
-        let idx = src as usize;
-        let frac = src - idx as f64;
-        let s = if idx + 1 < samples.len() {
-            let a = samples[idx] as f64;
-            let b = samples[idx + 1] as f64;
-            (a + frac * (b - a)) as i16
+
+    let g = gcd(from_rate, to_rate);
+    let up = (to_rate / g) as i64;
+    let down = (from_rate / g) as i64;
+    let factor = up.max(down) as f64;
+
+    // Cutoff and kernel half-length expressed in intermediate (up-sampled) samples.
+    let fc = 0.45 / factor; // cycles per intermediate sample
+    let half = (16.0 * factor) as i64;
+
+    // Prototype windowed-sinc kernel, indexed by intermediate-sample offset from the center.
+    let taps = (2 * half + 1) as usize;
+    let mut proto = vec![0.0f64; taps];
+    for (k, tap) in proto.iter_mut().enumerate() {
+        let x = k as f64 - half as f64;
+        let sinc = if x == 0.0 {
+            2.0 * fc
         } else {
-            samples[idx.min(samples.len() - 1)]
+            (2.0 * std::f64::consts::PI * fc * x).sin() / (std::f64::consts::PI * x)
         };
-        out.push(s);
+        let hann = 0.5 - 0.5 * (2.0 * std::f64::consts::PI * k as f64 / (taps - 1) as f64).cos();
+        *tap = sinc * hann;
+    }
+
+    let out_len = (samples.len() as i64 * up / down).max(0) as usize;
+    let mut out = Vec::with_capacity(out_len);
+    for m in 0..out_len as i64 {
+        let n = m * down; // position in the intermediate (up-sampled) grid
+        // Input sample j sits at intermediate position j*up; include those within ±half.
+        let j_lo = ((n - half) as f64 / up as f64).ceil() as i64;
+        let j_hi = ((n + half) as f64 / up as f64).floor() as i64;
+        let mut acc = 0.0f64;
+        let mut wsum = 0.0f64;
+        for j in j_lo..=j_hi {
+            if j < 0 || j as usize >= samples.len() {
+                continue;
+            }
+            let k = (n - j * up) + half; // kernel index
+            if k < 0 || k as usize >= taps {
+                continue;
+            }
+            let w = proto[k as usize];
+            acc += samples[j as usize] as f64 * w;
+            wsum += w;
+        }
+        let v = if wsum.abs() > 1e-9 { acc / wsum } else { 0.0 };
+        out.push(v.round().clamp(i16::MIN as f64, i16::MAX as f64) as i16);
     }
     out
 }
 
 #[tauri::command]
-pub fn stop_recording(state: State<'_, AudioState>) -> Result<String, String> {
-    do_stop_recording(state)
+pub fn stop_recording(app: AppHandle, state: State<'_, AudioState>) -> Result<String, String> {
+    let store = app.state::<Store>();
+    do_stop_recording(state, store.is_noise_reduction_enabled(), store.is_trim_silence_enabled())
+}
+
+/// Trim leading and trailing runs of sub-threshold samples, keeping `TRIM_PADDING_MS` of padding on
+/// each side so the first and last words aren't clipped. Returns the input unchanged when it is all
+/// silence or too short to analyse.
+fn trim_silence(samples: &[i16], rate: u32) -> Vec<i16> {
+    let window = (rate as usize / 100).max(1); // ~10 ms analysis windows
+    if samples.len() <= window * 2 {
+        return samples.to_vec();
+    }
+
+    // Per-window RMS, then an adaptive threshold relative to the quietest window and the peak.
+    let windows: Vec<f64> = samples
+        .chunks(window)
+        .map(|w| {
+            let sum: f64 = w.iter().map(|&s| (s as f64).powi(2)).sum();
+            (sum / w.len() as f64).sqrt()
+        })
+        .collect();
+    let noise_floor = windows.iter().cloned().fold(f64::INFINITY, f64::min);
+    let peak = windows.iter().cloned().fold(0.0_f64, f64::max);
+    let threshold = (noise_floor * SILENCE_NOISE_FACTOR).max(peak * 0.02);
+
+    let first = windows.iter().position(|&r| r > threshold);
+    let last = windows.iter().rposition(|&r| r > threshold);
+    let (Some(first), Some(last)) = (first, last) else {
+        return samples.to_vec(); // nothing above threshold — leave it for the caller to discard
+    };
+
+    let pad = (rate * TRIM_PADDING_MS / 1000) as usize;
+    let start = (first * window).saturating_sub(pad);
+    let end = ((last + 1) * window + pad).min(samples.len());
+    samples[start..end].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::downsample;
+
+    /// Generate a `secs`-long sine at `freq` Hz sampled at `rate`.
+    fn tone(freq: f64, rate: u32, secs: f64, amp: f64) -> Vec<i16> {
+        let n = (rate as f64 * secs) as usize;
+        (0..n)
+            .map(|i| {
+                let t = i as f64 / rate as f64;
+                (amp * (2.0 * std::f64::consts::PI * freq * t).sin()).round() as i16
+            })
+            .collect()
+    }
+
+    fn rms(samples: &[i16]) -> f64 {
+        if samples.is_empty() {
+            return 0.0;
+        }
+        let sum: f64 = samples.iter().map(|&s| (s as f64).powi(2)).sum();
+        (sum / samples.len() as f64).sqrt()
+    }
+
+    /// Gain in dB of the output relative to the input, measured away from the filter's edge ramps.
+    fn gain_db(input: &[i16], output: &[i16]) -> f64 {
+        let trim = output.len() / 8;
+        let core = &output[trim..output.len() - trim];
+        20.0 * (rms(core) / rms(input)).log10()
+    }
+
+    #[test]
+    fn passband_tone_survives_48k_to_16k() {
+        // 1 kHz is well inside the 16 kHz output band and should come through essentially intact.
+        let input = tone(1_000.0, 48_000, 0.5, 10_000.0);
+        let output = downsample(&input, 48_000, 16_000);
+        let db = gain_db(&input, &output);
+        assert!(db > -3.0, "1 kHz tone lost too much energy: {db:.1} dB");
+    }
+
+    #[test]
+    fn above_nyquist_tone_is_attenuated_48k_to_16k() {
+        // 15 kHz is far above the 8 kHz output Nyquist; the anti-aliasing filter must crush it so it
+        // doesn't fold back into the speech band.
+        let input = tone(15_000.0, 48_000, 0.5, 10_000.0);
+        let output = downsample(&input, 48_000, 16_000);
+        let db = gain_db(&input, &output);
+        assert!(db < -40.0, "15 kHz tone not attenuated enough: {db:.1} dB");
+    }
 }
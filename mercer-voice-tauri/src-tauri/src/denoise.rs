@@ -0,0 +1,111 @@
+//! Offline spectral-gating noise reduction applied to a finished capture before it is written to
+//! WAV. A short-time Fourier transform (`realfft`) gives per-frame magnitude/phase; a per-bin
+//! noise floor estimated from the quietest frames drives a soft gain that attenuates bins near the
+//! floor while leaving speech intact. Runs after `stop` so latency is not a concern.
+
+use realfft::RealFftPlanner;
+
+/// 32 ms frames at 16 kHz with 50% overlap.
+const FRAME_LEN: usize = 512;
+const HOP_LEN: usize = FRAME_LEN / 2;
+/// Over-subtraction factor: a bin at or below `BETA` noise floors is silenced; above that,
+/// `BETA` floors' worth of magnitude is subtracted from it.
+const BETA: f32 = 1.5;
+/// Fraction of the quietest frames used to estimate the per-bin noise floor.
+const NOISE_FRAME_FRACTION: f32 = 0.1;
+
+/// Spectral-gate `samples` (mono, `sample_rate` Hz). Returns the cleaned signal, or the input
+/// unchanged when it is too short to analyse.
+pub fn spectral_gate(samples: &[i16], _sample_rate: u32) -> Vec<i16> {
+    if samples.len() < FRAME_LEN * 2 {
+        return samples.to_vec();
+    }
+
+    let hann: Vec<f32> = (0..FRAME_LEN)
+        .map(|n| {
+            let x = std::f32::consts::PI * n as f32 / (FRAME_LEN as f32 - 1.0);
+            x.sin().powi(2)
+        })
+        .collect();
+
+    let mut planner = RealFftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(FRAME_LEN);
+    let ifft = planner.plan_fft_inverse(FRAME_LEN);
+    let num_bins = FRAME_LEN / 2 + 1;
+
+    // No padding: samples past the last whole frame pass through unprocessed (see the `norm`
+    // fallback below).
+    let float: Vec<f32> = samples.iter().map(|&s| s as f32 / i16::MAX as f32).collect();
+    let frame_count = (float.len() - FRAME_LEN) / HOP_LEN + 1;
+
+    // Forward-transform every frame, keeping the complex spectra and per-bin magnitudes.
+    let mut spectra: Vec<Vec<realfft::num_complex::Complex<f32>>> = Vec::with_capacity(frame_count);
+    let mut magnitudes: Vec<Vec<f32>> = Vec::with_capacity(frame_count);
+    let mut scratch = fft.make_input_vec();
+    for f in 0..frame_count {
+        let start = f * HOP_LEN;
+        for (i, s) in scratch.iter_mut().enumerate() {
+            *s = float[start + i] * hann[i];
+        }
+        let mut spectrum = fft.make_output_vec();
+        if fft.process(&mut scratch, &mut spectrum).is_err() {
+            return samples.to_vec();
+        }
+        let mags: Vec<f32> = spectrum.iter().map(|c| c.norm()).collect();
+        spectra.push(spectrum);
+        magnitudes.push(mags);
+    }
+
+    // Per-bin noise floor: mean magnitude over the quietest frames (by total energy).
+    let mut order: Vec<usize> = (0..frame_count).collect();
+    order.sort_by(|&a, &b| {
+        let ea: f32 = magnitudes[a].iter().map(|m| m * m).sum();
+        let eb: f32 = magnitudes[b].iter().map(|m| m * m).sum();
+        ea.partial_cmp(&eb).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let noise_frames = ((frame_count as f32 * NOISE_FRAME_FRACTION).ceil() as usize).max(1);
+    let mut noise = vec![0.0f32; num_bins];
+    for &f in order.iter().take(noise_frames) {
+        for (bin, m) in magnitudes[f].iter().enumerate() {
+            noise[bin] += m;
+        }
+    }
+    for n in noise.iter_mut() {
+        *n /= noise_frames as f32;
+    }
+
+    // Apply the soft gain to each bin and inverse-transform, windowing + overlap-adding back.
+    let mut out = vec![0.0f32; float.len()];
+    let mut norm = vec![0.0f32; float.len()];
+    let mut time = ifft.make_output_vec();
+    for f in 0..frame_count {
+        let mut spectrum = spectra[f].clone();
+        for (bin, c) in spectrum.iter_mut().enumerate() {
+            let mag = magnitudes[f][bin];
+            let gain = if mag > 0.0 {
+                ((mag - BETA * noise[bin]) / mag).max(0.0)
+            } else {
+                0.0
+            };
+            *c *= gain;
+        }
+        if ifft.process(&mut spectrum, &mut time).is_err() {
+            return samples.to_vec();
+        }
+        let start = f * HOP_LEN;
+        for i in 0..FRAME_LEN {
+            // realfft's inverse is unnormalized, so divide by FRAME_LEN.
+            out[start + i] += time[i] / FRAME_LEN as f32 * hann[i];
+            norm[start + i] += hann[i] * hann[i];
+        }
+    }
+
+    out.iter()
+        .zip(norm.iter())
+        .enumerate()
+        .map(|(i, (&v, &w))| {
+            let sample = if w > 1e-6 { v / w } else { float[i] };
+            (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+        })
+        .collect()
+}
@@ -0,0 +1,37 @@
+//! Tiny event bus over Tauri's `emit_filter`. Each payload is serialized once and fanned out to
+//! every window whose label matches the chosen audience, so new surfaces (pill, dashboard, a
+//! future settings window) start receiving updates without any command having to name windows.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, EventTarget};
+
+/// Which windows an event should reach.
+#[derive(Clone, Copy)]
+pub enum Audience {
+    /// Every open window.
+    All,
+    /// Every window except the dictation pill (`main`) — e.g. stats the pill never shows.
+    ExceptPill,
+}
+
+impl Audience {
+    fn accepts(self, label: &str) -> bool {
+        match self {
+            Audience::All => true,
+            Audience::ExceptPill => label != "main",
+        }
+    }
+}
+
+/// Emit `event` with `payload` to all windows in `audience`, serializing `payload` a single time.
+pub fn broadcast<S: Serialize + Clone>(
+    app: &AppHandle,
+    audience: Audience,
+    event: &str,
+    payload: S,
+) {
+    let _ = app.emit_filter(event, payload, |target| match target {
+        EventTarget::WebviewWindow { label } => audience.accepts(label),
+        _ => false,
+    });
+}
@@ -1,10 +1,9 @@
-use std::sync::atomic::AtomicBool;
-#[cfg(target_os = "windows")]
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
-use std::sync::OnceLock;
-use tauri::{AppHandle, Emitter, Manager};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Emitter, Listener, Manager};
 
+use crate::accelerator::Accelerator;
 use crate::audio;
 use crate::hotkey_state::HotkeyState;
 use crate::paste;
@@ -21,11 +20,19 @@ mod macos {
     use std::sync::atomic::{AtomicPtr, Ordering};
 
     pub const KVK_FUNCTION: i64 = 0x3F;
+    pub const KCGEVENT_KEY_DOWN: u32 = 10;
+    pub const KCGEVENT_KEY_UP: u32 = 11;
     pub const KCGEVENT_FLAGS_CHANGED: u32 = 12;
     pub const KCGEVENT_TAP_DISABLED_BY_TIMEOUT: u32 = 0xFFFFFFFE;
     pub const KCG_EVENT_FLAG_MASK_SECONDARY_FN: u64 = 0x0080_0000;
     pub const KCG_KEYBOARD_EVENT_KEYCODE: u32 = 9;
 
+    // CGEventFlags modifier masks.
+    const FLAG_SHIFT: u64 = 0x0002_0000;
+    const FLAG_CONTROL: u64 = 0x0004_0000;
+    const FLAG_ALT: u64 = 0x0008_0000;
+    const FLAG_COMMAND: u64 = 0x0010_0000;
+
     pub const KCG_HID_EVENT_TAP: u32 = 0;
     pub const KCG_HEAD_INSERT_EVENT_TAP: u32 = 0;
     pub const KCG_EVENT_TAP_OPTION_LISTEN_ONLY: u32 = 1;
@@ -69,6 +76,54 @@ mod macos {
 
     pub static TAP_PORT: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
 
+    /// Translate macOS modifier flags into our accelerator modifier bitmask.
+    fn flags_to_mods(flags: u64) -> u8 {
+        use crate::accelerator::modifiers as m;
+        let mut mods = 0u8;
+        if flags & FLAG_SHIFT != 0 {
+            mods |= m::SHIFT;
+        }
+        if flags & FLAG_CONTROL != 0 {
+            mods |= m::CONTROL;
+        }
+        if flags & FLAG_ALT != 0 {
+            mods |= m::ALT;
+        }
+        if flags & FLAG_COMMAND != 0 {
+            mods |= m::META;
+        }
+        mods
+    }
+
+    /// Map an ANSI virtual keycode to the accelerator `KeyCode` the parser produces, so a live key
+    /// event can be compared against a bound chord. `None` for keys we don't spell accelerators in.
+    /// F21–F24 have no macOS key code here, so bindings to them never match.
+    fn keycode_to_key(code: i64) -> Option<crate::accelerator::KeyCode> {
+        use crate::accelerator::KeyCode as K;
+        Some(match code {
+            0 => K::Char('A'), 11 => K::Char('B'), 8 => K::Char('C'), 2 => K::Char('D'),
+            14 => K::Char('E'), 3 => K::Char('F'), 5 => K::Char('G'), 4 => K::Char('H'),
+            34 => K::Char('I'), 38 => K::Char('J'), 40 => K::Char('K'), 37 => K::Char('L'),
+            46 => K::Char('M'), 45 => K::Char('N'), 31 => K::Char('O'), 35 => K::Char('P'),
+            12 => K::Char('Q'), 15 => K::Char('R'), 1 => K::Char('S'), 17 => K::Char('T'),
+            32 => K::Char('U'), 9 => K::Char('V'), 13 => K::Char('W'), 7 => K::Char('X'),
+            16 => K::Char('Y'), 6 => K::Char('Z'),
+            29 => K::Char('0'), 18 => K::Char('1'), 19 => K::Char('2'), 20 => K::Char('3'),
+            21 => K::Char('4'), 23 => K::Char('5'), 22 => K::Char('6'), 26 => K::Char('7'),
+            28 => K::Char('8'), 25 => K::Char('9'),
+            49 => K::Space, 48 => K::Tab,
+            43 => K::Punct(','), 27 => K::Punct('-'), 47 => K::Punct('.'), 24 => K::Punct('='),
+            41 => K::Punct(';'), 44 => K::Punct('/'), 42 => K::Punct('\\'), 39 => K::Punct('\''),
+            50 => K::Punct('`'), 33 => K::Punct('['), 30 => K::Punct(']'),
+            122 => K::Function(1), 120 => K::Function(2), 99 => K::Function(3), 118 => K::Function(4),
+            96 => K::Function(5), 97 => K::Function(6), 98 => K::Function(7), 100 => K::Function(8),
+            101 => K::Function(9), 109 => K::Function(10), 103 => K::Function(11), 111 => K::Function(12),
+            105 => K::Function(13), 107 => K::Function(14), 113 => K::Function(15), 106 => K::Function(16),
+            64 => K::Function(17), 79 => K::Function(18), 80 => K::Function(19), 90 => K::Function(20),
+            _ => return None,
+        })
+    }
+
     pub extern "C" fn tap_callback(
         _proxy: *mut c_void,
         event_type: u32,
@@ -85,27 +140,45 @@ mod macos {
             return event;
         }
 
-        if event_type != KCGEVENT_FLAGS_CHANGED {
-            return event;
-        }
-
-        let keycode = unsafe { CGEventGetIntegerValueField(event, KCG_KEYBOARD_EVENT_KEYCODE) };
-        if keycode != KVK_FUNCTION {
-            return event;
-        }
-
-        let flags = unsafe { CGEventGetFlags(event) };
-        let fn_down = flags & KCG_EVENT_FLAG_MASK_SECONDARY_FN != 0;
-
-        if fn_down && !super::HOTKEY_ACTIVE.load(Ordering::SeqCst) {
-            super::HOTKEY_ACTIVE.store(true, Ordering::SeqCst);
-            if let Some(tx) = super::EVENT_TX.get() {
-                let _ = tx.send(super::HotkeyEvent::Press);
+        match super::active_accelerator() {
+            // No bound chord: fall back to the default Fn/Globe key via modifier-change events.
+            None => {
+                if event_type != KCGEVENT_FLAGS_CHANGED {
+                    return event;
+                }
+                let keycode = unsafe { CGEventGetIntegerValueField(event, KCG_KEYBOARD_EVENT_KEYCODE) };
+                if keycode != KVK_FUNCTION {
+                    return event;
+                }
+                let flags = unsafe { CGEventGetFlags(event) };
+                if flags & KCG_EVENT_FLAG_MASK_SECONDARY_FN != 0 {
+                    super::trigger_press();
+                } else {
+                    super::trigger_release();
+                }
             }
-        } else if !fn_down && super::HOTKEY_ACTIVE.load(Ordering::SeqCst) {
-            super::HOTKEY_ACTIVE.store(false, Ordering::SeqCst);
-            if let Some(tx) = super::EVENT_TX.get() {
-                let _ = tx.send(super::HotkeyEvent::Release);
+            // A bound chord like "Cmd+Shift+V": match the live key + modifiers on key down/up.
+            Some(acc) => {
+                if event_type != KCGEVENT_KEY_DOWN && event_type != KCGEVENT_KEY_UP {
+                    return event;
+                }
+                let keycode = unsafe { CGEventGetIntegerValueField(event, KCG_KEYBOARD_EVENT_KEYCODE) };
+                let Some(key) = keycode_to_key(keycode) else {
+                    return event;
+                };
+                if key != acc.key {
+                    return event;
+                }
+                if event_type == KCGEVENT_KEY_DOWN {
+                    // Only fire when the modifier set matches exactly (ignores key auto-repeat).
+                    let flags = unsafe { CGEventGetFlags(event) };
+                    if flags_to_mods(flags) == acc.mods {
+                        super::trigger_press();
+                    }
+                } else {
+                    // Release on key-up regardless of modifiers — the user may lift them first.
+                    super::trigger_release();
+                }
             }
         }
 
@@ -124,9 +197,115 @@ enum HotkeyEvent {
 
 static EVENT_TX: OnceLock<mpsc::Sender<HotkeyEvent>> = OnceLock::new();
 
+/// The chord the listener triggers on. `None` means the platform default (Fn/Globe on macOS, Right
+/// Ctrl on Windows). Read live by the tap/listener on every key event so `set_hotkey` takes effect
+/// without a restart.
+static ACTIVE_ACCEL: OnceLock<Mutex<Option<Accelerator>>> = OnceLock::new();
+
+fn accel_cell() -> &'static Mutex<Option<Accelerator>> {
+    ACTIVE_ACCEL.get_or_init(|| Mutex::new(None))
+}
+
+/// Set (or clear, with `None`) the accelerator the hotkey listener matches against.
+pub fn set_active_accelerator(accelerator: Option<Accelerator>) {
+    *accel_cell().lock().unwrap() = accelerator;
+}
+
+fn active_accelerator() -> Option<Accelerator> {
+    accel_cell().lock().unwrap().clone()
+}
+
+/// Send a single `Press` edge when the chord transitions from up to down.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn trigger_press() {
+    if !HOTKEY_ACTIVE.swap(true, Ordering::SeqCst) {
+        if let Some(tx) = EVENT_TX.get() {
+            let _ = tx.send(HotkeyEvent::Press);
+        }
+    }
+}
+
+/// Send a single `Release` edge when the chord transitions from down to up.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn trigger_release() {
+    if HOTKEY_ACTIVE.swap(false, Ordering::SeqCst) {
+        if let Some(tx) = EVENT_TX.get() {
+            let _ = tx.send(HotkeyEvent::Release);
+        }
+    }
+}
+
+// ── Incremental streaming: feed live capture into the on-device transcriber ──
+
+/// A live streaming session tied to one press→release: the transcriber handle plus the
+/// `audio-chunk` subscription feeding it captured windows.
+struct StreamSession {
+    handle: transcribe::StreamHandle,
+    listener: tauri::EventId,
+}
+
+impl StreamSession {
+    /// Stop consuming chunks and return the reconciled transcript. Empty output maps to `None` so
+    /// the caller falls back to the one-shot WAV pass.
+    fn finish(self, app: &AppHandle) -> Option<String> {
+        app.unlisten(self.listener);
+        match self.handle.finish() {
+            Ok(text) if !text.trim().is_empty() => Some(text),
+            Ok(_) => None,
+            Err(e) => {
+                eprintln!("[Verba] Streaming finalize failed: {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Start a streaming session when the on-device model is selected and streaming is enabled,
+/// forwarding each `audio-chunk` window to the transcriber and re-emitting its partials as
+/// `streaming-update`. Returns `None` for cloud backends or when no local model is resolved.
+fn start_stream_session(app: &AppHandle) -> Option<StreamSession> {
+    let store = app.state::<Store>();
+    if store.transcription_source() != "local" {
+        return None;
+    }
+    if !audio::is_streaming(&app.state::<audio::AudioState>()) {
+        return None;
+    }
+    let model_path = store.resolve_local_model_path()?;
+
+    let app_for_update = app.clone();
+    let handle = transcribe::start_local_stream(model_path, move |update| {
+        let _ = app_for_update.emit_to("main", "streaming-update", update);
+    });
+
+    // `audio-chunk` windows overlap by STREAM_OVERLAP_MS, so every window after the first repeats its
+    // leading overlap; drop it before appending or the transcriber would hear stuttered audio.
+    let feeder = handle.feeder();
+    let first = std::sync::Mutex::new(true);
+    let listener = app.listen("audio-chunk", move |event| {
+        let Ok(chunk) = serde_json::from_str::<audio::AudioChunk>(event.payload()) else {
+            return;
+        };
+        let mut first = first.lock().unwrap();
+        let samples: &[i16] = if *first {
+            *first = false;
+            &chunk.samples
+        } else {
+            let overlap =
+                (chunk.sample_rate as u64 * audio::STREAM_OVERLAP_MS / 1000) as usize;
+            let skip = overlap.min(chunk.samples.len());
+            &chunk.samples[skip..]
+        };
+        feeder.push(samples);
+    });
+    Some(StreamSession { handle, listener })
+}
+
 // ── Worker thread — all heavy work happens here, off the tap thread ───
 
 fn run_worker(rx: mpsc::Receiver<HotkeyEvent>, app_handle: AppHandle) {
+    // A live on-device streaming session runs between a press and its release (local model only).
+    let mut active_stream: Option<StreamSession> = None;
     while let Ok(evt) = rx.recv() {
         match evt {
             HotkeyEvent::Press => {
@@ -143,10 +322,13 @@ fn run_worker(rx: mpsc::Receiver<HotkeyEvent>, app_handle: AppHandle) {
                         eprintln!("[Verba] start_recording failed: {}", e);
                     }
                 });
+                // Feed the capture stream into an incremental transcriber for live partials.
+                active_stream = start_stream_session(&app_handle);
             }
             HotkeyEvent::Release => {
                 sounds::play_boop();
                 eprintln!("[Verba] Hotkey RELEASED");
+                let stream = active_stream.take();
                 let app = app_handle.clone();
                 let _ = app_handle.run_on_main_thread(move || {
                     let wav_path = match audio::stop_recording_impl(&app) {
@@ -157,16 +339,50 @@ fn run_worker(rx: mpsc::Receiver<HotkeyEvent>, app_handle: AppHandle) {
                         }
                     };
                     let bundle_id = app.state::<HotkeyState>().take_paste_target();
-                    // Resolve API credentials from store before async spawn
+                    // Resolve transcription config + credentials from store before async spawn
                     let store: tauri::State<'_, Store> = app.state();
+                    let source = store.transcription_source();
                     let api_endpoint = store.resolve_endpoint();
                     let api_key = store.resolve_api_key();
+                    let local_model_path = store.resolve_local_model_path();
+                    let openai = Some(transcribe::OpenAiCredentials {
+                        endpoint: store.resolve_openai_endpoint(),
+                        api_key: store.resolve_openai_api_key(),
+                        model: store.resolve_openai_model(),
+                    });
+                    let prompt = store.vocabulary_prompt();
+                    let trim = store.is_trim_silence_enabled();
                     let app_for_paste = app.clone();
+                    let app_for_stream = app.clone();
                     tauri::async_runtime::spawn(async move {
                         eprintln!("[Verba] Transcribing...");
-                        match transcribe::transcribe_impl(wav_path, api_endpoint, api_key).await {
+                        // Prefer the already-reconciled streaming transcript when one was running;
+                        // otherwise fall back to a one-shot pass over the recorded WAV.
+                        let result = match stream.and_then(|s| s.finish(&app_for_stream)) {
+                            Some(text) => Ok(text),
+                            None => {
+                                transcribe::transcribe_impl(
+                                    wav_path,
+                                    source,
+                                    api_endpoint,
+                                    api_key,
+                                    local_model_path,
+                                    openai,
+                                    prompt,
+                                    trim,
+                                )
+                                .await
+                            }
+                        };
+                        match result {
                             Ok(text) if !text.is_empty() => {
                                 eprintln!("[Verba] Pasting into target app");
+                                // Apply the full replacement pipeline (vocabulary then dictionary)
+                                // before paste, matching the `transcribe` command path.
+                                let text = {
+                                    let store: tauri::State<'_, Store> = app_for_paste.state();
+                                    store.apply_replacements(&text)
+                                };
                                 let text_for_stats = text.clone();
                                 let app_for_stats = app_for_paste.clone();
                                 let paste_target = Some(bundle_id.unwrap_or_default());
@@ -176,9 +392,11 @@ fn run_worker(rx: mpsc::Receiver<HotkeyEvent>, app_handle: AppHandle) {
                                     store.record_dictation(&text_for_stats);
                                     let _ = app_for_stats.emit("stats-updated", ());
 
-                                    if let Err(e) =
-                                        paste::paste_text_impl(text, paste_target)
-                                    {
+                                    if let Err(e) = paste::paste_text_impl(
+                                        text,
+                                        paste_target,
+                                        paste::InjectMode::Paste,
+                                    ) {
                                         eprintln!("[Verba] paste failed: {}", e);
                                     }
                                 });
@@ -252,7 +470,10 @@ pub fn start_hotkey_listener(app_handle: AppHandle) {
 
         let app_for_error = app_handle;
         std::thread::spawn(move || {
-            let event_mask: u64 = 1 << macos::KCGEVENT_FLAGS_CHANGED;
+            // Flags-changed powers the default Fn chord; key down/up power bound chords.
+            let event_mask: u64 = (1 << macos::KCGEVENT_FLAGS_CHANGED)
+                | (1 << macos::KCGEVENT_KEY_DOWN)
+                | (1 << macos::KCGEVENT_KEY_UP);
 
             let port = unsafe {
                 macos::CGEventTapCreate(
@@ -287,36 +508,56 @@ pub fn start_hotkey_listener(app_handle: AppHandle) {
                 let run_loop = macos::CFRunLoopGetCurrent();
                 macos::CFRunLoopAddSource(run_loop, source, macos::kCFRunLoopCommonModes);
                 macos::CGEventTapEnable(port, true);
-                eprintln!("[Verba] Global hotkey listener started (Fn/Globe key, HID tap)");
+                eprintln!("[Verba] Global hotkey listener started (Fn/Globe key or bound chord, HID tap)");
                 macos::CFRunLoopRun();
             }
         });
     }
 
-    // ── Windows: rdev global listener for Right Ctrl ───────────────
+    // ── Windows: rdev global listener (Right Ctrl default, or the bound chord) ──
     #[cfg(target_os = "windows")]
     {
         std::thread::spawn(move || {
-            eprintln!("[Verba] Starting global hotkey listener (Right Ctrl, rdev)");
-            if let Err(e) = rdev::listen(|event| {
-                match event.event_type {
-                    rdev::EventType::KeyPress(rdev::Key::ControlRight) => {
-                        if !HOTKEY_ACTIVE.load(Ordering::SeqCst) {
-                            HOTKEY_ACTIVE.store(true, Ordering::SeqCst);
-                            if let Some(tx) = EVENT_TX.get() {
-                                let _ = tx.send(HotkeyEvent::Press);
+            eprintln!("[Verba] Starting global hotkey listener (Right Ctrl / bound chord, rdev)");
+            // rdev events carry no modifier state, so track it from the modifier key edges.
+            let mut mods = 0u8;
+            if let Err(e) = rdev::listen(move |event| {
+                let (key, pressed) = match event.event_type {
+                    rdev::EventType::KeyPress(k) => (k, true),
+                    rdev::EventType::KeyRelease(k) => (k, false),
+                    _ => return,
+                };
+                if let Some(bit) = windows_modifier_bit(key) {
+                    if pressed {
+                        mods |= bit;
+                    } else {
+                        mods &= !bit;
+                    }
+                }
+
+                match active_accelerator() {
+                    // No bound chord: the default push-to-talk key is Right Ctrl.
+                    None => {
+                        if key == rdev::Key::ControlRight {
+                            if pressed {
+                                trigger_press();
+                            } else {
+                                trigger_release();
                             }
                         }
                     }
-                    rdev::EventType::KeyRelease(rdev::Key::ControlRight) => {
-                        if HOTKEY_ACTIVE.load(Ordering::SeqCst) {
-                            HOTKEY_ACTIVE.store(false, Ordering::SeqCst);
-                            if let Some(tx) = EVENT_TX.get() {
-                                let _ = tx.send(HotkeyEvent::Release);
+                    Some(acc) => {
+                        if windows_key_to_keycode(key) != Some(acc.key) {
+                            return;
+                        }
+                        if pressed {
+                            if mods == acc.mods {
+                                trigger_press();
                             }
+                        } else {
+                            trigger_release();
                         }
                     }
-                    _ => {}
                 }
             }) {
                 eprintln!("[Verba] rdev listen error: {:?}", e);
@@ -324,3 +565,50 @@ pub fn start_hotkey_listener(app_handle: AppHandle) {
         });
     }
 }
+
+/// The accelerator modifier bit for an rdev modifier key, or `None` for non-modifier keys.
+#[cfg(target_os = "windows")]
+fn windows_modifier_bit(key: rdev::Key) -> Option<u8> {
+    use crate::accelerator::modifiers as m;
+    Some(match key {
+        rdev::Key::ShiftLeft | rdev::Key::ShiftRight => m::SHIFT,
+        rdev::Key::ControlLeft | rdev::Key::ControlRight => m::CONTROL,
+        rdev::Key::Alt | rdev::Key::AltGr => m::ALT,
+        rdev::Key::MetaLeft | rdev::Key::MetaRight => m::META,
+        _ => return None,
+    })
+}
+
+/// Map an rdev key to the accelerator `KeyCode` the parser produces, so a live event can be matched
+/// against a bound chord. `None` for keys we don't spell accelerators in. Only F1–F12 are mapped,
+/// so bindings to F13–F24 never match on Windows.
+#[cfg(target_os = "windows")]
+fn windows_key_to_keycode(key: rdev::Key) -> Option<crate::accelerator::KeyCode> {
+    use crate::accelerator::KeyCode as K;
+    use rdev::Key;
+    Some(match key {
+        Key::KeyA => K::Char('A'), Key::KeyB => K::Char('B'), Key::KeyC => K::Char('C'),
+        Key::KeyD => K::Char('D'), Key::KeyE => K::Char('E'), Key::KeyF => K::Char('F'),
+        Key::KeyG => K::Char('G'), Key::KeyH => K::Char('H'), Key::KeyI => K::Char('I'),
+        Key::KeyJ => K::Char('J'), Key::KeyK => K::Char('K'), Key::KeyL => K::Char('L'),
+        Key::KeyM => K::Char('M'), Key::KeyN => K::Char('N'), Key::KeyO => K::Char('O'),
+        Key::KeyP => K::Char('P'), Key::KeyQ => K::Char('Q'), Key::KeyR => K::Char('R'),
+        Key::KeyS => K::Char('S'), Key::KeyT => K::Char('T'), Key::KeyU => K::Char('U'),
+        Key::KeyV => K::Char('V'), Key::KeyW => K::Char('W'), Key::KeyX => K::Char('X'),
+        Key::KeyY => K::Char('Y'), Key::KeyZ => K::Char('Z'),
+        Key::Num0 => K::Char('0'), Key::Num1 => K::Char('1'), Key::Num2 => K::Char('2'),
+        Key::Num3 => K::Char('3'), Key::Num4 => K::Char('4'), Key::Num5 => K::Char('5'),
+        Key::Num6 => K::Char('6'), Key::Num7 => K::Char('7'), Key::Num8 => K::Char('8'),
+        Key::Num9 => K::Char('9'),
+        Key::Space => K::Space, Key::Tab => K::Tab,
+        Key::Comma => K::Punct(','), Key::Minus => K::Punct('-'), Key::Dot => K::Punct('.'),
+        Key::Equal => K::Punct('='), Key::SemiColon => K::Punct(';'), Key::Slash => K::Punct('/'),
+        Key::BackSlash => K::Punct('\\'), Key::Quote => K::Punct('\''), Key::BackQuote => K::Punct('`'),
+        Key::LeftBracket => K::Punct('['), Key::RightBracket => K::Punct(']'),
+        Key::F1 => K::Function(1), Key::F2 => K::Function(2), Key::F3 => K::Function(3),
+        Key::F4 => K::Function(4), Key::F5 => K::Function(5), Key::F6 => K::Function(6),
+        Key::F7 => K::Function(7), Key::F8 => K::Function(8), Key::F9 => K::Function(9),
+        Key::F10 => K::Function(10), Key::F11 => K::Function(11), Key::F12 => K::Function(12),
+        _ => return None,
+    })
+}
@@ -1,21 +1,30 @@
+mod accelerator;
 mod audio;
+mod denoise;
+mod events;
 mod hotkey;
 mod hotkey_state;
 mod paste;
 mod permissions;
+mod pill_hover;
 mod sounds;
 mod store;
 mod transcribe;
+mod vad;
 
 use std::path::Path;
 use futures_util::StreamExt;
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 use tokio::io::AsyncWriteExt;
-use store::{ApiConfig, DictionaryEntry, Settings, Stats, Store, TranscriptionConfig};
+use store::{
+    ApiConfig, DictionaryEntry, OpenAiConfig, ProxyConfig, Settings, Stats, Store,
+    TranscriptionConfig, Vocabulary,
+};
 use tauri::menu::{MenuBuilder, MenuItemBuilder};
 use tauri::tray::TrayIconBuilder;
 use tauri::PhysicalPosition;
-use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
 
 // ===== DASHBOARD WINDOW =====
 
@@ -58,8 +67,8 @@ fn get_stats(store: tauri::State<'_, Store>) -> Stats {
 #[tauri::command]
 fn record_dictation(app: tauri::AppHandle, store: tauri::State<'_, Store>, text: String) {
     store.record_dictation(&text);
-    // Notify dashboard (if open) to refresh
-    let _ = app.emit("stats-updated", ());
+    // Notify every stats surface to refresh (the pill shows no stats, so skip it).
+    events::broadcast(&app, events::Audience::ExceptPill, "stats-updated", ());
 }
 
 #[tauri::command]
@@ -103,6 +112,13 @@ fn remove_dictionary_entry(
     store.remove_dictionary_entry(&id)
 }
 
+/// Apply the dictionary's replacement entries to a transcript. Invoked between `transcribe` and
+/// `paste_text` when the frontend drives the two steps itself.
+#[tauri::command]
+fn apply_dictionary(store: tauri::State<'_, Store>, text: String) -> String {
+    store.apply_dictionary(&text)
+}
+
 // ===== SETTINGS COMMANDS =====
 
 #[tauri::command]
@@ -111,8 +127,54 @@ fn get_settings(store: tauri::State<'_, Store>) -> Settings {
 }
 
 #[tauri::command]
-fn update_setting(store: tauri::State<'_, Store>, key: String, value: bool) {
+fn update_setting(app: tauri::AppHandle, store: tauri::State<'_, Store>, key: String, value: bool) {
     store.update_setting(&key, value);
+    if key == "pill_on_all_workspaces" {
+        apply_pill_workspace_visibility(&app, value);
+    }
+}
+
+/// Pin (or release) the dictation pill across every macOS Space and above fullscreen apps.
+fn apply_pill_workspace_visibility(app: &tauri::AppHandle, on: bool) {
+    if let Some(main_win) = app.get_webview_window("main") {
+        let _ = main_win.set_visible_on_all_workspaces(on);
+    }
+}
+
+#[tauri::command]
+fn set_sound_theme(store: tauri::State<'_, Store>, theme: String) {
+    store.set_sound_theme(theme);
+    sounds::configure(store.sounds_dir(), store.get_sound_volume());
+}
+
+#[tauri::command]
+fn set_sound_volume(store: tauri::State<'_, Store>, volume: f32) {
+    store.set_sound_volume(volume);
+    sounds::configure(store.sounds_dir(), store.get_sound_volume());
+}
+
+// ===== HOTKEY COMMANDS =====
+
+#[tauri::command]
+fn get_hotkey(store: tauri::State<'_, Store>) -> String {
+    store.get_hotkey()
+}
+
+/// Validate and persist the dictation hotkey accelerator. Returns a parse error for invalid strings.
+#[tauri::command]
+fn set_hotkey(
+    app: tauri::AppHandle,
+    store: tauri::State<'_, Store>,
+    accelerator: String,
+) -> Result<(), String> {
+    let parsed = if accelerator.trim().is_empty() {
+        None
+    } else {
+        Some(accelerator::Accelerator::parse(&accelerator)?)
+    };
+    store.set_hotkey(accelerator);
+    hotkey::set_active_accelerator(parsed);
+    Ok(())
 }
 
 // ===== API CONFIG COMMANDS =====
@@ -127,6 +189,47 @@ fn set_api_config(store: tauri::State<'_, Store>, endpoint: String, api_key: Str
     store.set_api_config(endpoint, api_key);
 }
 
+// ===== PROXY CONFIG COMMANDS =====
+
+#[tauri::command]
+fn get_proxy_config(store: tauri::State<'_, Store>) -> ProxyConfig {
+    store.get_proxy_config()
+}
+
+#[tauri::command]
+fn set_proxy_config(store: tauri::State<'_, Store>, url: String) {
+    store.set_proxy_config(url);
+}
+
+// ===== VOCABULARY COMMANDS =====
+
+#[tauri::command]
+fn get_vocabulary(store: tauri::State<'_, Store>) -> Vocabulary {
+    store.get_vocabulary()
+}
+
+#[tauri::command]
+fn set_vocabulary(store: tauri::State<'_, Store>, vocabulary: Vocabulary) {
+    store.set_vocabulary(vocabulary);
+}
+
+// ===== OPENAI-COMPATIBLE CONFIG =====
+
+#[tauri::command]
+fn get_openai_config(store: tauri::State<'_, Store>) -> OpenAiConfig {
+    store.get_openai_config()
+}
+
+#[tauri::command]
+fn set_openai_config(
+    store: tauri::State<'_, Store>,
+    endpoint: String,
+    api_key: String,
+    model: String,
+) {
+    store.set_openai_config(endpoint, api_key, model);
+}
+
 // ===== TRANSCRIPTION CONFIG (Azure vs Local) =====
 
 #[tauri::command]
@@ -140,8 +243,9 @@ fn set_transcription_config(
     source: String,
     local_model_path: String,
     local_model_size: String,
+    language: Option<String>,
 ) {
-    store.set_transcription_config(source, local_model_path, local_model_size);
+    store.set_transcription_config(source, local_model_path, local_model_size, language);
 }
 
 #[tauri::command]
@@ -153,18 +257,23 @@ fn get_default_local_model_path(store: tauri::State<'_, Store>) -> Option<String
 fn get_default_local_model_path_for_size(
     store: tauri::State<'_, Store>,
     size: String,
+    language: Option<String>,
 ) -> Option<String> {
-    store.get_default_local_model_path_for_size(&size)
+    store.get_default_local_model_path_for_size(&size, language.as_deref())
 }
 
-const GGML_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
-
 #[derive(Clone, Serialize)]
 struct DownloadProgress {
     loaded: u64,
     total: u64,
 }
 
+/// Resumable, checksum-verified download of the ggml model for `size` into the models directory.
+/// This command is the single model fetcher for the app: it streams into a `.part` sidecar,
+/// resumes an interrupted transfer with a `Range` header, verifies length + SHA-256 against the
+/// pinned manifest (see `store::model_integrity_for_file`; files not pinned there are not
+/// verified), and only then atomically renames the file into place. A connection dropped
+/// mid-transfer fails the call; the next call resumes from the `.part` file.
 #[tauri::command]
 async fn download_local_model(
     app: AppHandle,
@@ -178,16 +287,12 @@ async fn download_local_model(
     } else {
         "tiny".to_string()
     };
-    let filename = match size.as_str() {
-        "small" => "ggml-small.en.bin",
-        "medium" => "ggml-medium.en.bin",
-        "large" => "ggml-large-v3.bin",
-        _ => "ggml-tiny.en.bin",
-    };
-    let url = format!("{}/{}", GGML_BASE_URL, filename);
+    let filename = store.model_filename_for(&size);
+    let filename = filename.as_str();
+    let url = format!("{}/{}", store::GGML_BASE_URL, filename);
 
     let path_str = store
-        .get_default_local_model_path_for_size(&size)
+        .get_default_local_model_path_for_size(&size, None)
         .ok_or_else(|| "Could not get default model path".to_string())?;
     let path = Path::new(&path_str).to_path_buf();
     let parent = path
@@ -195,13 +300,31 @@ async fn download_local_model(
         .ok_or_else(|| "Invalid model path".to_string())?;
     std::fs::create_dir_all(parent).map_err(|e| format!("Could not create models folder: {}", e))?;
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(600))
+    // Route through an explicit proxy if one is configured, otherwise the standard
+    // HTTPS_PROXY / ALL_PROXY environment variables (SOCKS5 URLs included).
+    let mut builder = reqwest::Client::builder().timeout(std::time::Duration::from_secs(600));
+    if let Some(proxy_url) = store.resolve_proxy() {
+        let proxy = reqwest::Proxy::all(&proxy_url)
+            .map_err(|e| format!("Invalid proxy '{}': {}", proxy_url, e))?;
+        builder = builder.proxy(proxy);
+    }
+    let client = builder
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
-    let response = client
-        .get(&url)
+    // Download into a sidecar `.part` file so an interrupted transfer can be resumed: if one is
+    // already on disk we ask the server to continue from where it left off with a Range header.
+    let part_path = path.with_extension("part");
+    let existing = tokio::fs::metadata(&part_path)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+
+    let mut request = client.get(&url);
+    if existing > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", existing));
+    }
+    let response = request
         .send()
         .await
         .map_err(|e| format!("Download failed: {}", e))?;
@@ -213,18 +336,48 @@ async fn download_local_model(
         ));
     }
 
-    let total = response.content_length().unwrap_or(0);
-    let mut stream = response.bytes_stream();
-    let mut file = tokio::fs::File::create(&path)
-        .await
-        .map_err(|e| format!("Failed to create model file: {}", e))?;
-    let mut loaded: u64 = 0;
+    // A 206 means the server honored our Range and we can append; anything else (including a plain
+    // 200 when a `.part` existed) means we must start the file over from scratch.
+    let resuming = existing > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+
+    let mut loaded: u64 = if resuming { existing } else { 0 };
+    // Content-Length on a 206 covers only the remaining bytes, so add what we already have.
+    let total = response.content_length().unwrap_or(0) + if resuming { existing } else { 0 };
     let mut last_emit_pct = 0u8;
+    // Hash the bytes as they arrive so we can reject a corrupted or tampered download before it
+    // is ever loaded by whisper. When resuming, seed the hasher with the bytes already on disk.
+    let mut hasher = Sha256::new();
+
+    let mut file = if resuming {
+        let pct = if total > 0 { ((existing as f64 / total as f64) * 100.0) as u8 } else { 0 };
+        last_emit_pct = pct;
+        events::broadcast(
+            &app,
+            events::Audience::ExceptPill,
+            "model-download-resumed",
+            DownloadProgress { loaded, total },
+        );
+        let existing_bytes = tokio::fs::read(&part_path)
+            .await
+            .map_err(|e| format!("Failed to read partial download: {}", e))?;
+        hasher.update(&existing_bytes);
+        tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&part_path)
+            .await
+            .map_err(|e| format!("Failed to open partial download: {}", e))?
+    } else {
+        tokio::fs::File::create(&part_path)
+            .await
+            .map_err(|e| format!("Failed to create model file: {}", e))?
+    };
 
+    let mut stream = response.bytes_stream();
     while let Some(chunk) = stream.next().await {
         let bytes = chunk.map_err(|e| format!("Download stream error: {}", e))?;
         let len = bytes.len() as u64;
         loaded += len;
+        hasher.update(&bytes);
         tokio::io::AsyncWriteExt::write_all(&mut file, &bytes)
             .await
             .map_err(|e| format!("Failed to write model file: {}", e))?;
@@ -235,8 +388,9 @@ async fn download_local_model(
         };
         if pct >= last_emit_pct + 2 || loaded == len || (total > 0 && loaded >= total) {
             last_emit_pct = pct;
-            let _ = app.emit_to(
-                "dashboard",
+            events::broadcast(
+                &app,
+                events::Audience::ExceptPill,
                 "model-download-progress",
                 DownloadProgress { loaded, total },
             );
@@ -247,6 +401,24 @@ async fn download_local_model(
         .await
         .map_err(|e| format!("Failed to flush model file: {}", e))?;
 
+    // Verify size and digest against the pinned manifest. A mismatch means a truncated download
+    // or a tampered mirror — delete the partial file so the next attempt starts clean.
+    if let Some((expected_len, expected_sha)) = store::model_integrity_for_file(filename) {
+        let digest = format!("{:x}", hasher.finalize());
+        if loaded != expected_len || digest != expected_sha {
+            let _ = tokio::fs::remove_file(&part_path).await;
+            return Err(format!(
+                "Model verification failed for {} (got {} bytes / {}, expected {} bytes / {})",
+                filename, loaded, digest, expected_len, expected_sha
+            ));
+        }
+    }
+
+    // Only promote the sidecar to the final path once the transfer is complete and verified.
+    tokio::fs::rename(&part_path, &path)
+        .await
+        .map_err(|e| format!("Failed to finalize model file: {}", e))?;
+
     eprintln!("[Verba] Downloaded {} to {}", filename, path_str);
     Ok(path_str)
 }
@@ -256,6 +428,16 @@ async fn download_local_model(
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // Single-instance must be the first plugin registered so a second launch is intercepted
+        // before any hotkey/tray state is set up. The callback runs in the primary instance: we
+        // ignore the forwarded args and just surface the existing app (pill + dashboard).
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            if let Some(main_win) = app.get_webview_window("main") {
+                let _ = main_win.show();
+                let _ = main_win.set_focus();
+            }
+            open_dashboard_window(app);
+        }))
         .plugin(tauri_plugin_shell::init())
         .manage(audio::AudioState::default())
         .manage(hotkey_state::HotkeyState::default())
@@ -271,14 +453,45 @@ pub fn run() {
                 store.init(app_data_dir);
             }
 
+            // Point the feedback sounds at the saved theme/volume, creating the themes dir so
+            // users have a known place to drop start.wav/stop.wav.
+            {
+                let store: tauri::State<'_, Store> = app.state();
+                if let Some(dir) = store.sounds_dir() {
+                    let _ = std::fs::create_dir_all(&dir);
+                }
+                sounds::configure(store.sounds_dir(), store.get_sound_volume());
+            }
+
             permissions::check_and_request_permissions();
 
+            // Parse the saved hotkey accelerator (if any) so the listener reads its chord from it.
+            {
+                let store: tauri::State<'_, Store> = app.state();
+                let hk = store.get_hotkey();
+                if !hk.trim().is_empty() {
+                    match accelerator::Accelerator::parse(&hk) {
+                        Ok(acc) => hotkey::set_active_accelerator(Some(acc)),
+                        Err(e) => eprintln!("[Verba] Ignoring invalid saved hotkey \"{}\": {}", hk, e),
+                    }
+                }
+            }
+
             let app_handle = app.handle().clone();
             hotkey::start_hotkey_listener(app_handle);
 
+            // Global mouse tap that powers pill hover and carries live modifier state.
+            pill_hover::start_pill_hover_listener(app.handle().clone());
+
             // Build system tray
             build_tray(app)?;
 
+            // Re-apply the saved all-Spaces pin for the pill on startup.
+            {
+                let on = app.state::<Store>().is_pill_on_all_workspaces();
+                apply_pill_workspace_visibility(&app.handle().clone(), on);
+            }
+
             // Dock pill at bottom-center of primary monitor on first load
             if let Some(main_win) = app.get_webview_window("main") {
                 if let Ok(Some(monitor)) = main_win.primary_monitor() {
@@ -299,7 +512,14 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             audio::start_recording,
             audio::stop_recording,
+            audio::list_input_devices,
+            audio::set_input_device,
+            audio::set_capture_source,
+            audio::set_streaming,
+            audio::flush_stream,
             transcribe::transcribe,
+            transcribe::transcribe_structured,
+            transcribe::export_subtitles,
             paste::paste_text,
             open_dashboard,
             get_stats,
@@ -309,10 +529,21 @@ pub fn run() {
             add_dictionary_entry,
             update_dictionary_entry,
             remove_dictionary_entry,
+            apply_dictionary,
             get_settings,
             update_setting,
+            set_sound_theme,
+            set_sound_volume,
+            get_hotkey,
+            set_hotkey,
             get_api_config,
             set_api_config,
+            get_openai_config,
+            set_openai_config,
+            get_vocabulary,
+            set_vocabulary,
+            get_proxy_config,
+            set_proxy_config,
             get_transcription_config,
             set_transcription_config,
             get_default_local_model_path,
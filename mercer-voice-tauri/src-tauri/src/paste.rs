@@ -1,16 +1,102 @@
-use arboard::Clipboard;
+use arboard::{Clipboard, ImageData};
 use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+use std::borrow::Cow;
 use std::panic::{self, AssertUnwindSafe};
 use std::process::Command;
 use std::thread;
 use std::time::Duration;
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Our app's bundle id — we never activate ourselves.
 const VERBA_BUNDLE_ID: &str = "app.verba";
 
+/// Delay between synthesized keystrokes in `InjectMode::Type`, so apps that debounce or
+/// monitor input keep up with us.
+const INTER_KEY_DELAY: Duration = Duration::from_millis(4);
+
+/// How the transcribed text reaches the target app.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum InjectMode {
+    /// Default: set the clipboard and send Cmd+V (snapshot/restore applies).
+    Paste,
+    /// Type each character as a synthetic key event; never touches the clipboard. Useful for
+    /// secure fields and apps that monitor or reject synthetic paste.
+    Type,
+}
+
+impl Default for InjectMode {
+    fn default() -> Self {
+        InjectMode::Paste
+    }
+}
+
+/// How long to wait after Cmd+V before restoring the user's original clipboard, so the
+/// target app has already consumed our paste before we overwrite it.
+const CLIPBOARD_RESTORE_DELAY: Duration = Duration::from_millis(600);
+
+/// Snapshot of whatever the user had on the clipboard before we clobbered it, so we can put
+/// it back afterwards. Only the formats arboard can round-trip are captured.
+enum ClipboardSnapshot {
+    Text(String),
+    Image { width: usize, height: usize, bytes: Vec<u8> },
+    Empty,
+}
+
+impl ClipboardSnapshot {
+    /// Capture the current clipboard contents (text first, then image), or `Empty` if unreadable.
+    fn capture(clipboard: &mut Clipboard) -> Self {
+        if let Ok(text) = clipboard.get_text() {
+            if !text.is_empty() {
+                return ClipboardSnapshot::Text(text);
+            }
+        }
+        if let Ok(img) = clipboard.get_image() {
+            return ClipboardSnapshot::Image {
+                width: img.width,
+                height: img.height,
+                bytes: img.bytes.into_owned(),
+            };
+        }
+        ClipboardSnapshot::Empty
+    }
+
+    /// Write the captured contents back to the clipboard. No-op for `Empty`.
+    fn restore(self, clipboard: &mut Clipboard) {
+        match self {
+            ClipboardSnapshot::Text(text) => {
+                let _ = clipboard.set_text(text);
+            }
+            ClipboardSnapshot::Image { width, height, bytes } => {
+                let _ = clipboard.set_image(ImageData {
+                    width,
+                    height,
+                    bytes: Cow::Owned(bytes),
+                });
+            }
+            ClipboardSnapshot::Empty => {}
+        }
+    }
+}
+
 /// Core paste logic. Used by both the Tauri command and the hotkey flow.
-pub fn paste_text_impl(text: String, target_bundle_id: Option<String>) -> Result<(), String> {
-    let result = panic::catch_unwind(AssertUnwindSafe(|| do_paste(text, target_bundle_id)));
+pub fn paste_text_impl(
+    text: String,
+    target_bundle_id: Option<String>,
+    mode: InjectMode,
+) -> Result<(), String> {
+    // Modifier-driven paste variants, read off the mouse tap: holding Option switches to
+    // direct-typing, holding Shift keeps the clipboard path but suppresses the Cmd+V keystroke.
+    let mods = crate::pill_hover::current_modifiers();
+    let (mode, suppress_keystroke) = if mods.option {
+        (InjectMode::Type, false)
+    } else {
+        (mode, mods.shift)
+    };
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| match mode {
+        InjectMode::Paste => do_paste(text, target_bundle_id, suppress_keystroke),
+        InjectMode::Type => do_type(text, target_bundle_id),
+    }));
     match result {
         Ok(Ok(())) => Ok(()),
         Ok(Err(e)) => Err(e),
@@ -28,7 +114,11 @@ pub fn paste_text_impl(text: String, target_bundle_id: Option<String>) -> Result
     }
 }
 
-fn do_paste(text: String, target_bundle_id: Option<String>) -> Result<(), String> {
+fn do_paste(
+    text: String,
+    target_bundle_id: Option<String>,
+    suppress_keystroke: bool,
+) -> Result<(), String> {
     if text.trim().is_empty() {
         return Ok(());
     }
@@ -36,13 +126,17 @@ fn do_paste(text: String, target_bundle_id: Option<String>) -> Result<(), String
     let mut clipboard =
         Clipboard::new().map_err(|e| format!("Failed to access clipboard: {}", e))?;
 
+    // Snapshot the user's existing clipboard so we can restore it after the paste lands.
+    let snapshot = ClipboardSnapshot::capture(&mut clipboard);
+
     clipboard
         .set_text(&text)
         .map_err(|e| format!("Failed to set clipboard text: {}", e))?;
 
     thread::sleep(Duration::from_millis(50));
 
-    let do_keystroke = target_bundle_id.is_some();
+    // Shift held: leave our text on the clipboard but don't paste it (clipboard-only).
+    let do_keystroke = target_bundle_id.is_some() && !suppress_keystroke;
 
     #[cfg(target_os = "macos")]
     if let Some(ref bid) = target_bundle_id {
@@ -101,10 +195,80 @@ fn do_paste(text: String, target_bundle_id: Option<String>) -> Result<(), String
         eprintln!("[Verba] Paste: Cmd+V sent");
     }
 
+    // Restore the original clipboard on a background thread once the target app has consumed
+    // our paste. Skipped when there was nothing to restore, or in clipboard-only mode where the
+    // user still needs our text on the clipboard to paste manually.
+    if do_keystroke && !matches!(snapshot, ClipboardSnapshot::Empty) {
+        thread::spawn(move || {
+            thread::sleep(CLIPBOARD_RESTORE_DELAY);
+            match Clipboard::new() {
+                Ok(mut clipboard) => {
+                    snapshot.restore(&mut clipboard);
+                    eprintln!("[Verba] Paste: original clipboard restored");
+                }
+                Err(e) => eprintln!("[Verba] Paste: could not restore clipboard: {}", e),
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Type `text` into the target app one grapheme at a time via synthetic key events.
+/// Never touches the clipboard, so the user's clipboard and clipboard-monitoring apps are
+/// left untouched.
+fn do_type(text: String, target_bundle_id: Option<String>) -> Result<(), String> {
+    if text.trim().is_empty() {
+        return Ok(());
+    }
+    eprintln!("[Verba] Type: injecting {} chars via synthetic keys", text.len());
+
+    #[cfg(target_os = "macos")]
+    if let Some(ref bid) = target_bundle_id {
+        let bid = bid.trim();
+        let valid_target = !bid.is_empty()
+            && !bid.eq_ignore_ascii_case("missing value")
+            && bid != VERBA_BUNDLE_ID;
+        if valid_target {
+            let script = format!(
+                r#"tell application "System Events" to set frontmost of first process whose bundle identifier is "{}" to true"#,
+                bid.replace('"', "\\\"")
+            );
+            let _ = Command::new("osascript").args(["-e", &script]).output();
+            thread::sleep(Duration::from_millis(200));
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = &target_bundle_id;
+
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|e| format!("Failed to create Enigo (grant Accessibility?): {}", e))?;
+
+    for cluster in text.graphemes(true) {
+        match cluster {
+            "\n" => enigo
+                .key(Key::Return, Direction::Click)
+                .map_err(|e| format!("Key click failed: {}", e))?,
+            "\t" => enigo
+                .key(Key::Tab, Direction::Click)
+                .map_err(|e| format!("Key click failed: {}", e))?,
+            _ => {
+                // A grapheme cluster may carry combining marks; emit each scalar in order so
+                // the base character and its combining marks land together.
+                for c in cluster.chars() {
+                    enigo
+                        .key(Key::Unicode(c), Direction::Click)
+                        .map_err(|e| format!("Key click failed: {}", e))?;
+                }
+            }
+        }
+        thread::sleep(INTER_KEY_DELAY);
+    }
+    eprintln!("[Verba] Type: done");
     Ok(())
 }
 
 #[tauri::command]
 pub fn paste_text(text: String, target_bundle_id: Option<String>) -> Result<(), String> {
-    do_paste(text, target_bundle_id)
+    do_paste(text, target_bundle_id, false)
 }
@@ -13,6 +13,27 @@ use tauri::Manager;
 /// Extra pixels around the window to trigger "over" slightly early.
 const HIT_SLOP: i32 = 12;
 
+/// Live keyboard-modifier state, read off mouse events (no keyboard tap needed). Snapshot it
+/// with [`current_modifiers`] at paste time to pick a modifier-driven paste variant.
+#[derive(Clone, Copy, Default)]
+pub struct ModifierState {
+    pub shift: bool,
+    pub control: bool,
+    pub option: bool,
+    pub command: bool,
+}
+
+/// Snapshot the modifiers carried on the most recent mouse event.
+#[cfg(target_os = "macos")]
+pub fn current_modifiers() -> ModifierState {
+    macos_tap::current_modifiers()
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn current_modifiers() -> ModifierState {
+    ModifierState::default()
+}
+
 pub fn start_pill_hover_listener(app: tauri::AppHandle) {
     #[cfg(target_os = "macos")]
     macos_tap::start_pill_hover_listener_macos_tap(app);
@@ -32,13 +53,36 @@ mod macos_tap {
 
     use super::*;
 
+    const KCG_EVENT_LEFT_MOUSE_DOWN: u32 = 1;
+    const KCG_EVENT_RIGHT_MOUSE_DOWN: u32 = 3;
     const KCG_EVENT_MOUSE_MOVED: u32 = 5;
+    const KCG_EVENT_SCROLL_WHEEL: u32 = 22;
     const KCG_EVENT_TAP_DISABLED_BY_TIMEOUT: u32 = 0xFFFFFFFE;
+    const KCG_EVENT_TAP_DISABLED_BY_USER_INPUT: u32 = 0xFFFFFFFF;
     const KCG_HID_EVENT_TAP: u32 = 0;
     const KCG_HEAD_INSERT_EVENT_TAP: u32 = 0;
     const KCG_EVENT_TAP_OPTION_LISTEN_ONLY: u32 = 1;
     const THROTTLE_MS: u64 = 50;
 
+    // CGEventFlags masks (Carbon `kCGEventFlagMask*`).
+    const FLAG_MASK_SHIFT: u64 = 1 << 17;
+    const FLAG_MASK_CONTROL: u64 = 1 << 18;
+    const FLAG_MASK_ALTERNATE: u64 = 1 << 19;
+    const FLAG_MASK_COMMAND: u64 = 1 << 20;
+
+    /// Live modifier flags from the latest mouse event, updated without any keyboard tap.
+    static MOUSE_FLAGS: AtomicU64 = AtomicU64::new(0);
+
+    pub fn current_modifiers() -> super::ModifierState {
+        let flags = MOUSE_FLAGS.load(Ordering::Relaxed);
+        super::ModifierState {
+            shift: flags & FLAG_MASK_SHIFT != 0,
+            control: flags & FLAG_MASK_CONTROL != 0,
+            option: flags & FLAG_MASK_ALTERNATE != 0,
+            command: flags & FLAG_MASK_COMMAND != 0,
+        }
+    }
+
     #[repr(C)]
     struct CGPoint {
         x: f64,
@@ -66,6 +110,7 @@ mod macos_tap {
         ) -> CFMachPortRef;
         fn CGEventTapEnable(tap: CFMachPortRef, enable: bool);
         fn CGEventGetLocation(event: CGEventRef) -> CGPoint;
+        fn CGEventGetFlags(event: CGEventRef) -> u64;
         fn CFMachPortCreateRunLoopSource(
             allocator: *const c_void,
             port: CFMachPortRef,
@@ -77,8 +122,15 @@ mod macos_tap {
         static kCFRunLoopCommonModes: *const c_void;
     }
 
+    /// What the tap observed, forwarded to the receiver thread for hit-testing and emit.
+    pub enum TapEvent {
+        Move(i32, i32),
+        Click(i32, i32),
+        Scroll(i32, i32),
+    }
+
     static TAP_PORT: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
-    static PILL_HOVER_TX: std::sync::OnceLock<mpsc::Sender<(i32, i32)>> = std::sync::OnceLock::new();
+    static PILL_HOVER_TX: std::sync::OnceLock<mpsc::Sender<TapEvent>> = std::sync::OnceLock::new();
     static LAST_SENT_NS: AtomicU64 = AtomicU64::new(0);
 
     pub extern "C" fn mouse_tap_callback(
@@ -87,51 +139,85 @@ mod macos_tap {
         event: CGEventRef,
         _user_info: *mut c_void,
     ) -> CGEventRef {
-        if event_type == KCG_EVENT_TAP_DISABLED_BY_TIMEOUT {
+        // Both disable reasons silently kill the tap; re-enable for either so heavy input
+        // bursts can't leave hover permanently stuck.
+        if event_type == KCG_EVENT_TAP_DISABLED_BY_TIMEOUT
+            || event_type == KCG_EVENT_TAP_DISABLED_BY_USER_INPUT
+        {
             let port = TAP_PORT.load(Ordering::SeqCst);
             if !port.is_null() {
                 unsafe { CGEventTapEnable(port as CFMachPortRef, true); }
             }
             return event;
         }
-        if event_type != KCG_EVENT_MOUSE_MOVED {
-            return event;
-        }
+
+        // Record live modifier state on every event (no keyboard tap required).
+        MOUSE_FLAGS.store(unsafe { CGEventGetFlags(event) }, Ordering::Relaxed);
         let pt = unsafe { CGEventGetLocation(event) };
         let x = pt.x as i32;
         let y = pt.y as i32;
-        let now_ns = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos() as u64;
-        let min_interval_ns = THROTTLE_MS * 1_000_000;
-        if now_ns.saturating_sub(LAST_SENT_NS.load(Ordering::Relaxed)) < min_interval_ns {
-            return event;
-        }
-        LAST_SENT_NS.store(now_ns, Ordering::Relaxed);
-        if let Some(tx) = PILL_HOVER_TX.get() {
-            let _ = tx.send((x, y));
+
+        match event_type {
+            KCG_EVENT_LEFT_MOUSE_DOWN | KCG_EVENT_RIGHT_MOUSE_DOWN => {
+                if let Some(tx) = PILL_HOVER_TX.get() {
+                    let _ = tx.send(TapEvent::Click(x, y));
+                }
+            }
+            KCG_EVENT_SCROLL_WHEEL => {
+                if let Some(tx) = PILL_HOVER_TX.get() {
+                    let _ = tx.send(TapEvent::Scroll(x, y));
+                }
+            }
+            KCG_EVENT_MOUSE_MOVED => {
+                // Throttle hover hit-tests; clicks and scrolls above are never throttled.
+                let now_ns = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_nanos() as u64;
+                let min_interval_ns = THROTTLE_MS * 1_000_000;
+                if now_ns.saturating_sub(LAST_SENT_NS.load(Ordering::Relaxed)) >= min_interval_ns {
+                    LAST_SENT_NS.store(now_ns, Ordering::Relaxed);
+                    if let Some(tx) = PILL_HOVER_TX.get() {
+                        let _ = tx.send(TapEvent::Move(x, y));
+                    }
+                }
+            }
+            _ => {}
         }
         event
     }
 
     pub fn start_pill_hover_listener_macos_tap(app: tauri::AppHandle) {
-        let (tx, rx) = mpsc::channel::<(i32, i32)>();
+        let (tx, rx) = mpsc::channel::<TapEvent>();
         let _ = PILL_HOVER_TX.set(tx);
         let last_over = Arc::new(AtomicBool::new(false));
 
-        // Receiver thread: get (x,y) from tap, run hit-test on main thread and emit
+        // Receiver thread: get tap events, run hit-test on main thread and emit.
         let app_recv = app.clone();
         let last_over_recv = Arc::clone(&last_over);
         thread::spawn(move || {
-            while let Ok((cx, cy)) = rx.recv() {
+            while let Ok(evt) = rx.recv() {
                 let app_clone = app_recv.clone();
                 let last_over_clone = Arc::clone(&last_over_recv);
-                let _ = app_recv.run_on_main_thread(move || {
-                    let over = is_cursor_over_pill_window_at(&app_clone, cx, cy);
-                    if over != last_over_clone.load(Ordering::Relaxed) {
-                        last_over_clone.store(over, Ordering::Relaxed);
-                        let _ = app_clone.emit_to("main", "pill-cursor-over", over);
+                let _ = app_recv.run_on_main_thread(move || match evt {
+                    TapEvent::Move(cx, cy) => {
+                        let over = is_cursor_over_pill_window_at(&app_clone, cx, cy);
+                        if over != last_over_clone.load(Ordering::Relaxed) {
+                            last_over_clone.store(over, Ordering::Relaxed);
+                            let _ = app_clone.emit_to("main", "pill-cursor-over", over);
+                        }
+                    }
+                    // A click or scroll outside the pill collapses it / dismisses any preview,
+                    // the way a focused window loses focus.
+                    TapEvent::Click(cx, cy) => {
+                        if !is_cursor_over_pill_window_at(&app_clone, cx, cy) {
+                            let _ = app_clone.emit_to("main", "pill-click-outside", ());
+                        }
+                    }
+                    TapEvent::Scroll(cx, cy) => {
+                        if !is_cursor_over_pill_window_at(&app_clone, cx, cy) {
+                            let _ = app_clone.emit_to("main", "pill-scroll", ());
+                        }
                     }
                 });
             }
@@ -140,7 +226,10 @@ mod macos_tap {
         // Tap thread: run mouse-only event tap
         let app_fallback = app.clone();
         thread::spawn(move || {
-            let event_mask: u64 = 1 << KCG_EVENT_MOUSE_MOVED;
+            let event_mask: u64 = (1 << KCG_EVENT_MOUSE_MOVED)
+                | (1 << KCG_EVENT_LEFT_MOUSE_DOWN)
+                | (1 << KCG_EVENT_RIGHT_MOUSE_DOWN)
+                | (1 << KCG_EVENT_SCROLL_WHEEL);
             let port = unsafe {
                 CGEventTapCreate(
                     KCG_HID_EVENT_TAP,
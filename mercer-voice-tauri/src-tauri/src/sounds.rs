@@ -2,14 +2,19 @@
 //! Uses a single persistent audio stream to avoid open/close pops.
 
 use rodio::source::Source;
-use rodio::{OutputStream, Sink};
+use rodio::{Decoder, OutputStream, Sink};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::mpsc;
-use std::sync::OnceLock;
+use std::sync::{Mutex, OnceLock};
 use std::time::Duration;
 use std::thread;
 
 const SAMPLE_RATE: u32 = 48000;
-const VOLUME: f32 = 0.24;
+/// Default blip volume; overridden at runtime by the persisted `sound_volume` setting.
+const DEFAULT_VOLUME: f32 = 0.24;
 
 enum Sound {
     Start,
@@ -17,6 +22,33 @@ enum Sound {
 }
 
 static SENDER: OnceLock<mpsc::Sender<Sound>> = OnceLock::new();
+/// Directory of the active theme's WAV files. `None`/missing files fall back to synthesized tones.
+static SOUNDS_DIR: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+/// Current playback volume, stored as f32 bits so the audio thread can read it lock-free.
+/// Holds `VOLUME_UNSET` until `configure` runs.
+static VOLUME_BITS: AtomicU32 = AtomicU32::new(VOLUME_UNSET);
+/// Sentinel bit pattern (a NaN, never produced by `configure`) meaning "no volume set yet".
+const VOLUME_UNSET: u32 = u32::MAX;
+
+fn sounds_dir() -> &'static Mutex<Option<PathBuf>> {
+    SOUNDS_DIR.get_or_init(|| Mutex::new(None))
+}
+
+fn current_volume() -> f32 {
+    let bits = VOLUME_BITS.load(Ordering::Relaxed);
+    if bits == VOLUME_UNSET {
+        DEFAULT_VOLUME
+    } else {
+        f32::from_bits(bits)
+    }
+}
+
+/// Point the feedback sounds at a theme directory and set the playback volume. Called at startup
+/// and whenever the relevant settings change; safe to call before the audio thread exists.
+pub fn configure(dir: Option<PathBuf>, volume: f32) {
+    *sounds_dir().lock().unwrap() = dir;
+    VOLUME_BITS.store(volume.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+}
 
 fn sender() -> Option<&'static mpsc::Sender<Sound>> {
     SENDER.get_or_init(|| {
@@ -27,17 +59,38 @@ fn sender() -> Option<&'static mpsc::Sender<Sound>> {
     SENDER.get()
 }
 
+/// Path to the WAV for a sound in the active theme, if the file exists.
+fn theme_wav(sound: &Sound) -> Option<PathBuf> {
+    let name = match sound {
+        Sound::Start => "start.wav",
+        Sound::Stop => "stop.wav",
+    };
+    let dir = sounds_dir().lock().unwrap().clone()?;
+    let path = dir.join(name);
+    path.exists().then_some(path)
+}
+
 fn run_audio_thread(rx: mpsc::Receiver<Sound>) {
     let Ok((_stream, stream_handle)) = OutputStream::try_default() else { return };
     let Ok(sink) = Sink::try_new(&stream_handle) else { return };
-    sink.set_volume(VOLUME);
 
     while let Ok(sound) = rx.recv() {
-        let source = match sound {
-            Sound::Start => EnvelopeTone::new(380.0, 14, 70.0),
-            Sound::Stop => EnvelopeTone::new(280.0, 16, 65.0),
-        };
-        sink.append(source);
+        sink.set_volume(current_volume());
+        // Prefer a user-supplied WAV for the active theme; fall back to the synthesized blip if
+        // it is missing or fails to decode, keeping everything on the one persistent sink.
+        let decoded = theme_wav(&sound)
+            .and_then(|p| File::open(p).ok())
+            .and_then(|f| Decoder::new(BufReader::new(f)).ok());
+        match decoded {
+            Some(source) => sink.append(source),
+            None => {
+                let source = match sound {
+                    Sound::Start => EnvelopeTone::new(380.0, 14, 70.0),
+                    Sound::Stop => EnvelopeTone::new(280.0, 16, 65.0),
+                };
+                sink.append(source);
+            }
+        }
     }
 }
 
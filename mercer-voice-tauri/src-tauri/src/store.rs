@@ -1,7 +1,8 @@
+use aho_corasick::{AhoCorasick, MatchKind};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 // ===== DATA MODELS =====
 
@@ -43,6 +44,9 @@ pub struct ApiConfig {
     pub endpoint: String,
     #[serde(default)]
     pub api_key: String,
+    /// License-server base URL. Empty falls back to [`DEFAULT_LICENSE_ENDPOINT`].
+    #[serde(default)]
+    pub license_endpoint: String,
 }
 
 impl Default for ApiConfig {
@@ -50,6 +54,30 @@ impl Default for ApiConfig {
         Self {
             endpoint: String::new(),
             api_key: String::new(),
+            license_endpoint: String::new(),
+        }
+    }
+}
+
+/// OpenAI-compatible `/v1/audio/transcriptions` backend (real OpenAI, whisper.cpp server,
+/// LocalAI, edgen, …). Unlike Azure this authenticates with a `Bearer` token and needs a
+/// model name in the multipart form.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAiConfig {
+    #[serde(default)]
+    pub endpoint: String,
+    #[serde(default)]
+    pub api_key: String,
+    #[serde(default)]
+    pub model: String,
+}
+
+impl Default for OpenAiConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: String::new(),
+            api_key: String::new(),
+            model: "whisper-1".to_string(),
         }
     }
 }
@@ -65,6 +93,11 @@ pub struct TranscriptionConfig {
     /// Model size for default path and download: "tiny" | "small" | "medium" | "large".
     #[serde(default)]
     pub local_model_size: String,
+    /// Transcription language as an ISO code (e.g. "en", "de") or "auto". Anything other than
+    /// "en" selects the multilingual ggml model. Only the model file choice reads it: local
+    /// decoding always runs with the language set to English.
+    #[serde(default = "default_language")]
+    pub language: String,
 }
 
 impl Default for TranscriptionConfig {
@@ -73,14 +106,118 @@ impl Default for TranscriptionConfig {
             source: "azure".to_string(),
             local_model_path: String::new(),
             local_model_size: "tiny".to_string(),
+            language: default_language(),
         }
     }
 }
 
-/// Stored after a successful product key activation. Not the key itself.
+fn default_language() -> String {
+    "en".to_string()
+}
+
+/// Explicit proxy for model downloads. An empty `url` means "use the environment" — the
+/// standard `HTTPS_PROXY` / `ALL_PROXY` variables, including `socks5://` URLs.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ProxyConfig {
+    #[serde(default)]
+    pub url: String,
+}
+
+/// Stored after a successful product key activation. Holds the signed server response — never
+/// the key itself — so activation can be trusted offline within a grace window.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LicenseData {
     pub activated_at: u64,
+    /// When the server issued the entitlement (unix seconds).
+    #[serde(default)]
+    pub issued_at: u64,
+    /// When the entitlement expires (unix seconds); `0` = perpetual.
+    #[serde(default)]
+    pub expires_at: u64,
+    /// Install this license is bound to.
+    #[serde(default)]
+    pub machine_id: String,
+    /// Server signature over the response, carried so re-validation can present it.
+    #[serde(default)]
+    pub signature: String,
+    /// Last time the server confirmed the license (unix seconds); seeds the offline grace window.
+    #[serde(default)]
+    pub last_check: u64,
+}
+
+/// Default license server when `ApiConfig::license_endpoint` is unset.
+pub const DEFAULT_LICENSE_ENDPOINT: &str = "https://licenses.verba.app";
+
+/// Re-validate with the server at most this often (24 h).
+const LICENSE_RECHECK_SECS: u64 = 24 * 60 * 60;
+
+/// Keep trusting a cached license this long past the last successful check when offline (14 d).
+const LICENSE_GRACE_SECS: u64 = 14 * 24 * 60 * 60;
+
+/// Server activation/validation response.
+#[derive(Debug, Clone, Deserialize)]
+struct LicenseResponse {
+    #[serde(default)]
+    issued_at: u64,
+    #[serde(default)]
+    expires_at: u64,
+    #[serde(default)]
+    signature: String,
+}
+
+/// Unix time in seconds, saturating to 0 before the epoch.
+fn unix_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Stable per-install identifier the license is bound to. Derived from the license-file path
+/// (which lives under the per-user app data dir), so it survives restarts without storing PII.
+fn machine_id(license_path: &std::path::Path) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(license_path.to_string_lossy().as_bytes());
+    format!("{:x}", hasher.finalize())[..32].to_string()
+}
+
+/// POST `{key, machine_id}` to a license endpoint and parse the signed response.
+fn post_license(url: &str, key: &str, machine_id: &str) -> Result<LicenseResponse, String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let body = serde_json::json!({ "key": key.trim(), "machine_id": machine_id });
+    let resp = client
+        .post(url)
+        .json(&body)
+        .send()
+        .map_err(|e| format!("License server unreachable: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("License activation rejected: {}", e))?;
+    resp.json::<LicenseResponse>()
+        .map_err(|e| format!("Malformed license response: {}", e))
+}
+
+/// Re-validate a cached license off the hot path, refreshing `last_check`/expiry on success and
+/// leaving the cache untouched (so the grace window keeps it valid) when the server is unreachable.
+fn revalidate_license_in_background(endpoint: String, path: PathBuf, mut data: LicenseData) {
+    std::thread::spawn(move || {
+        let url = format!("{}/validate", endpoint);
+        if let Ok(resp) = post_license(&url, &data.signature, &data.machine_id) {
+            data.last_check = unix_secs();
+            if resp.expires_at != 0 {
+                data.expires_at = resp.expires_at;
+            }
+            if !resp.signature.is_empty() {
+                data.signature = resp.signature;
+            }
+            if let Ok(json) = serde_json::to_string_pretty(&data) {
+                let _ = fs::write(&path, json);
+            }
+        }
+    });
 }
 
 /// Validates a product key. Returns Ok(()) if valid.
@@ -108,25 +245,118 @@ fn validate_license_key(key: &str) -> Result<(), String> {
     Ok(())
 }
 
-/// Filename for the given model size (English .en models where available).
-fn model_filename_for_size(size: &str) -> &'static str {
-    match size {
-        "small" => "ggml-small.en.bin",
-        "medium" => "ggml-medium.en.bin",
-        "large" => "ggml-large-v3.bin",
-        _ => "ggml-tiny.en.bin",
+/// Filename for the given model size and language. English selects the smaller `.en` models where
+/// they exist; any other language (or "auto") selects the multilingual build.
+fn model_filename_for_size(size: &str, language: &str) -> &'static str {
+    let english = language == "en";
+    match (size, english) {
+        ("small", true) => "ggml-small.en.bin",
+        ("small", false) => "ggml-small.bin",
+        ("medium", true) => "ggml-medium.en.bin",
+        ("medium", false) => "ggml-medium.bin",
+        // Large ships multilingual-only upstream.
+        ("large", _) => "ggml-large-v3.bin",
+        (_, true) => "ggml-tiny.en.bin",
+        (_, false) => "ggml-tiny.bin",
+    }
+}
+
+/// Upstream host for the pinned ggml whisper.cpp models.
+pub(crate) const GGML_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+/// Expected `(byte length, SHA-256)` for a model filename — the single source of truth for model
+/// integrity, kept next to `model_filename_for_size`. The async download command verifies finished
+/// transfers against it. `None` for the multilingual builds we don't pin yet, so the downloader
+/// skips strict verification for them.
+pub(crate) fn model_integrity_for_file(filename: &str) -> Option<(u64, &'static str)> {
+    match filename {
+        "ggml-tiny.en.bin" => Some((
+            77_704_715,
+            "921e4cf8686fdd993dcd081a5da5b6c365bfde1162e72b08d75ac75289920b1f",
+        )),
+        "ggml-small.en.bin" => Some((
+            487_601_967,
+            "c6138d6d58ecc8322097e0f987c32f1be8bb0a18532a3f88f734d1bbf9c41e5d",
+        )),
+        "ggml-medium.en.bin" => Some((
+            1_533_763_059,
+            "cc37e93478338ec7700281a7ac30a10128929eb8f427dda2e865faa8f0a7f187",
+        )),
+        "ggml-large-v3.bin" => Some((
+            3_095_033_483,
+            "64d182b440b98d5203c4f9bd541544d84c605196c4f7b845dfa11fb23594d1e2",
+        )),
+        _ => None,
     }
 }
 
+/// One case-insensitive whole-word replacement applied to finished transcripts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VocabReplacement {
+    pub from: String,
+    pub to: String,
+}
+
+/// User-maintained vocabulary. `terms` bias decoding via Whisper's initial prompt (and the
+/// cloud backends' `prompt` field); `replacements` fix preferred spellings after the fact.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Vocabulary {
+    #[serde(default)]
+    pub terms: Vec<String>,
+    #[serde(default)]
+    pub replacements: Vec<VocabReplacement>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_sound_volume() -> f32 {
+    0.24
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
     pub sounds_enabled: bool,
     pub auto_paste: bool,
     pub launch_at_login: bool,
+    /// Keep the pill on every macOS Space and above fullscreen apps. Defaults on; users who find
+    /// it intrusive can turn it off.
+    #[serde(default = "default_true")]
+    pub pill_on_all_workspaces: bool,
+    /// Trim leading/trailing silence (and drop fully-silent captures) with the FFT VAD before
+    /// transcription. Defaults on.
+    #[serde(default = "default_true")]
+    pub trim_silence_enabled: bool,
+    /// Feedback-sound theme: a subfolder of `<app data>/sounds` holding `start.wav`/`stop.wav`.
+    /// Empty means `<app data>/sounds` itself; a missing or undecodable file plays the built-in
+    /// synthesized blip.
+    #[serde(default)]
+    pub sound_theme: String,
+    /// Playback volume for the feedback blips (0.0–1.0).
+    #[serde(default = "default_sound_volume")]
+    pub sound_volume: f32,
+    /// Run an STFT spectral-gate noise reduction pass over the capture before WAV export.
+    #[serde(default)]
+    pub noise_reduction_enabled: bool,
+    /// Automatically stop recording after a trailing run of silence (hands-free dictation).
+    /// Off by default so the hotkey stays authoritative.
+    #[serde(default)]
+    pub auto_stop_enabled: bool,
+    /// Accelerator string for the dictation/paste hotkey, e.g. `"Cmd+Shift+V"` or `"F13"`.
+    /// Empty = the platform default (Fn/Globe on macOS, Right Ctrl on Windows).
+    #[serde(default)]
+    pub hotkey: String,
     #[serde(default)]
     pub api_config: ApiConfig,
     #[serde(default)]
+    pub openai_config: OpenAiConfig,
+    #[serde(default)]
+    pub vocabulary: Vocabulary,
+    #[serde(default)]
     pub transcription: TranscriptionConfig,
+    #[serde(default)]
+    pub proxy_config: ProxyConfig,
 }
 
 impl Default for Settings {
@@ -135,8 +365,18 @@ impl Default for Settings {
             sounds_enabled: true,
             auto_paste: true,
             launch_at_login: false,
+            pill_on_all_workspaces: true,
+            trim_silence_enabled: true,
+            sound_theme: String::new(),
+            sound_volume: default_sound_volume(),
+            noise_reduction_enabled: false,
+            auto_stop_enabled: false,
+            hotkey: String::new(),
             api_config: ApiConfig::default(),
+            openai_config: OpenAiConfig::default(),
+            vocabulary: Vocabulary::default(),
             transcription: TranscriptionConfig::default(),
+            proxy_config: ProxyConfig::default(),
         }
     }
 }
@@ -165,6 +405,8 @@ pub struct Store {
     path: Mutex<Option<PathBuf>>,
     /// Set at init; used for default local model path.
     app_data_dir: Mutex<Option<PathBuf>>,
+    /// Lazily-built replacement automaton, invalidated on any dictionary edit. `None` = rebuild.
+    dictionary_rules: Mutex<Option<Arc<CompiledDictionary>>>,
 }
 
 impl Default for Store {
@@ -173,10 +415,19 @@ impl Default for Store {
             data: Mutex::new(StoreData::default()),
             path: Mutex::new(None),
             app_data_dir: Mutex::new(None),
+            dictionary_rules: Mutex::new(None),
         }
     }
 }
 
+/// Dictionary replacement rules compiled into a single Aho-Corasick automaton so a large
+/// dictionary costs one linear scan per dictation instead of O(rules · text).
+struct CompiledDictionary {
+    matcher: AhoCorasick,
+    /// Replacement text, indexed by pattern id from `matcher`.
+    replacements: Vec<String>,
+}
+
 impl Store {
     pub fn init(&self, app_data_dir: PathBuf) {
         let store_path = app_data_dir.join("store.json");
@@ -269,6 +520,7 @@ impl Store {
         let mut data = self.data.lock().unwrap();
         data.dictionary.push(entry.clone());
         drop(data);
+        self.invalidate_dictionary_rules();
         self.save();
         entry
     }
@@ -286,6 +538,7 @@ impl Store {
             entry.replacement = replacement;
             entry.entry_type = entry_type;
             drop(data);
+            self.invalidate_dictionary_rules();
             self.save();
             Ok(())
         } else {
@@ -301,10 +554,201 @@ impl Store {
             return Err("Entry not found".to_string());
         }
         drop(data);
+        self.invalidate_dictionary_rules();
         self.save();
         Ok(())
     }
 
+    /// Drop the cached automaton so the next `apply_dictionary` rebuilds from current entries.
+    fn invalidate_dictionary_rules(&self) {
+        *self.dictionary_rules.lock().unwrap() = None;
+    }
+
+    /// Whisper initial-prompt hint from boost entries (`custom`/`spelling`), so proper nouns
+    /// transcribe correctly in the first place. `None` when there is nothing to bias with.
+    pub fn dictionary_prompt(&self) -> Option<String> {
+        let phrases: Vec<String> = self
+            .data
+            .lock()
+            .unwrap()
+            .dictionary
+            .iter()
+            .filter(|e| matches!(e.entry_type.as_str(), "custom" | "spelling"))
+            .map(|e| e.phrase.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        if phrases.is_empty() {
+            None
+        } else {
+            Some(phrases.join(", "))
+        }
+    }
+
+    /// Build (or reuse) the replacement automaton for `entry_type == "replacement"` entries.
+    fn compiled_dictionary(&self) -> Option<Arc<CompiledDictionary>> {
+        if let Some(compiled) = self.dictionary_rules.lock().unwrap().clone() {
+            return Some(compiled);
+        }
+        let mut patterns: Vec<String> = Vec::new();
+        let mut replacements: Vec<String> = Vec::new();
+        for entry in self.data.lock().unwrap().dictionary.iter() {
+            if entry.entry_type != "replacement" {
+                continue;
+            }
+            let phrase = entry.phrase.trim();
+            let replacement = entry.replacement.as_deref().unwrap_or("").to_string();
+            if phrase.is_empty() {
+                continue;
+            }
+            patterns.push(phrase.to_string());
+            replacements.push(replacement);
+        }
+        if patterns.is_empty() {
+            return None;
+        }
+        // Leftmost-longest so a multi-word phrase wins over a single-word prefix; case-insensitive
+        // matching with case preservation applied when we splice the replacement in.
+        let matcher = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .ascii_case_insensitive(true)
+            .build(&patterns)
+            .ok()?;
+        let compiled = Arc::new(CompiledDictionary {
+            matcher,
+            replacements,
+        });
+        *self.dictionary_rules.lock().unwrap() = Some(compiled.clone());
+        Some(compiled)
+    }
+
+    /// Post-transcription dictionary pass over a finished transcript, run left-to-right and
+    /// non-overlapping:
+    /// - `replacement` entries: exact whole-word, case-insensitive substitution (Aho-Corasick).
+    /// - `blocked` entries: strip the matching phrase (and one trailing space) from the output.
+    /// - `custom` entries: correct near-miss tokens back to the canonical phrase using fuzzy
+    ///   (normalized Levenshtein) matching over windows of 1..N tokens (N = the phrase's word
+    ///   count), accepting distance ≤ 0.25, or ≤ 0.4 when the phonetic keys agree. A candidate
+    ///   spanning more tokens than the phrase has words is not compared, so e.g. "Cooper Netties"
+    ///   is not corrected to "Kubernetes".
+    ///
+    /// Matched tokens carry their casing onto the substituted text where possible.
+    pub fn apply_dictionary(&self, text: &str) -> String {
+        let text = self.apply_dictionary_replacements(text);
+        self.apply_dictionary_fuzzy(&text)
+    }
+
+    /// The exact replacement pass (Aho-Corasick automaton over `replacement` entries).
+    fn apply_dictionary_replacements(&self, text: &str) -> String {
+        let Some(compiled) = self.compiled_dictionary() else {
+            return text.to_string();
+        };
+        let bytes = text.as_bytes();
+        let mut out = String::with_capacity(text.len());
+        let mut last = 0;
+        for m in compiled.matcher.find_iter(text) {
+            let (start, end) = (m.start(), m.end());
+            let before_ok = start == 0 || !bytes[start - 1].is_ascii_alphanumeric();
+            let after_ok = end >= bytes.len() || !bytes[end].is_ascii_alphanumeric();
+            if !(before_ok && after_ok) {
+                continue;
+            }
+            out.push_str(&text[last..start]);
+            out.push_str(&match_replacement_case(
+                &text[start..end],
+                &compiled.replacements[m.pattern().as_usize()],
+            ));
+            last = end;
+        }
+        out.push_str(&text[last..]);
+        out
+    }
+
+    /// The `blocked` (strip) and `custom` (fuzzy/phonetic correction) passes.
+    fn apply_dictionary_fuzzy(&self, text: &str) -> String {
+        // Collect the canonical phrases for the two token-level passes.
+        let entries: Vec<(String, String)> = self
+            .data
+            .lock()
+            .unwrap()
+            .dictionary
+            .iter()
+            .filter(|e| matches!(e.entry_type.as_str(), "custom" | "blocked"))
+            .map(|e| (e.entry_type.clone(), e.phrase.trim().to_string()))
+            .filter(|(_, p)| !p.is_empty())
+            .collect();
+        if entries.is_empty() {
+            return text.to_string();
+        }
+        let max_words = entries
+            .iter()
+            .map(|(_, p)| p.split_whitespace().count())
+            .max()
+            .unwrap_or(1)
+            .max(1);
+
+        // Byte spans of each word (alphanumeric run) in the text.
+        let words = word_spans(text);
+        if words.is_empty() {
+            return text.to_string();
+        }
+
+        let mut out = String::with_capacity(text.len());
+        let mut cursor = 0usize; // bytes copied to `out` so far
+        let mut wi = 0usize;
+        while wi < words.len() {
+            let mut best: Option<(&str, &str, usize, f32)> = None; // (type, phrase, win_len, dist)
+            let remaining = words.len() - wi;
+            for win in 1..=max_words.min(remaining) {
+                let span_start = words[wi].0;
+                let span_end = words[wi + win - 1].1;
+                let candidate = &text[span_start..span_end];
+                for (etype, phrase) in &entries {
+                    if win > phrase.split_whitespace().count() {
+                        continue;
+                    }
+                    let dist = normalized_edit_distance(candidate, phrase);
+                    let acceptable = match etype.as_str() {
+                        // `blocked` must match closely (effectively exact) before we delete text.
+                        "blocked" => dist <= f32::EPSILON,
+                        // `custom` tolerates near-misses, tie-broken by phonetic equality.
+                        "custom" => {
+                            dist <= 0.25
+                                || (dist <= 0.4 && phonetic_key(candidate) == phonetic_key(phrase))
+                        }
+                        _ => false,
+                    };
+                    if acceptable && best.is_none_or(|(_, _, _, d)| dist < d) {
+                        best = Some((etype, phrase, win, dist));
+                    }
+                }
+            }
+
+            if let Some((etype, phrase, win, _)) = best {
+                out.push_str(&text[cursor..words[wi].0]);
+                let matched = &text[words[wi].0..words[wi + win - 1].1];
+                let mut new_cursor = words[wi + win - 1].1;
+                match etype {
+                    "custom" => out.push_str(&match_replacement_case(matched, phrase)),
+                    "blocked" => {
+                        // Drop the phrase; also swallow a single following space to avoid a gap.
+                        if text[new_cursor..].starts_with(' ') {
+                            new_cursor += 1;
+                        } else if out.ends_with(' ') {
+                            out.pop();
+                        }
+                    }
+                    _ => {}
+                }
+                cursor = new_cursor;
+                wi += win;
+            } else {
+                wi += 1;
+            }
+        }
+        out.push_str(&text[cursor..]);
+        out
+    }
+
     // --- Settings ---
 
     pub fn get_settings(&self) -> Settings {
@@ -317,12 +761,26 @@ impl Store {
             "sounds_enabled" => data.settings.sounds_enabled = value,
             "auto_paste" => data.settings.auto_paste = value,
             "launch_at_login" => data.settings.launch_at_login = value,
+            "pill_on_all_workspaces" => data.settings.pill_on_all_workspaces = value,
+            "trim_silence_enabled" => data.settings.trim_silence_enabled = value,
+            "noise_reduction_enabled" => data.settings.noise_reduction_enabled = value,
+            "auto_stop_enabled" => data.settings.auto_stop_enabled = value,
             _ => {}
         }
         drop(data);
         self.save();
     }
 
+    /// The configured hotkey accelerator string, or empty for the platform default.
+    pub fn get_hotkey(&self) -> String {
+        self.data.lock().unwrap().settings.hotkey.clone()
+    }
+
+    pub fn set_hotkey(&self, accelerator: String) {
+        self.data.lock().unwrap().settings.hotkey = accelerator;
+        self.save();
+    }
+
     #[allow(dead_code)]
     pub fn is_sounds_enabled(&self) -> bool {
         self.data.lock().unwrap().settings.sounds_enabled
@@ -333,6 +791,52 @@ impl Store {
         self.data.lock().unwrap().settings.auto_paste
     }
 
+    pub fn is_pill_on_all_workspaces(&self) -> bool {
+        self.data.lock().unwrap().settings.pill_on_all_workspaces
+    }
+
+    pub fn is_trim_silence_enabled(&self) -> bool {
+        self.data.lock().unwrap().settings.trim_silence_enabled
+    }
+
+    pub fn is_noise_reduction_enabled(&self) -> bool {
+        self.data.lock().unwrap().settings.noise_reduction_enabled
+    }
+
+    pub fn is_auto_stop_enabled(&self) -> bool {
+        self.data.lock().unwrap().settings.auto_stop_enabled
+    }
+
+    pub fn get_sound_theme(&self) -> String {
+        self.data.lock().unwrap().settings.sound_theme.clone()
+    }
+
+    pub fn set_sound_theme(&self, theme: String) {
+        self.data.lock().unwrap().settings.sound_theme = theme.trim().to_string();
+        self.save();
+    }
+
+    pub fn get_sound_volume(&self) -> f32 {
+        self.data.lock().unwrap().settings.sound_volume
+    }
+
+    pub fn set_sound_volume(&self, volume: f32) {
+        self.data.lock().unwrap().settings.sound_volume = volume.clamp(0.0, 1.0);
+        self.save();
+    }
+
+    /// Directory holding the active feedback-sound theme's `start.wav`/`stop.wav`, if any.
+    pub fn sounds_dir(&self) -> Option<PathBuf> {
+        let app_dir = self.app_data_dir.lock().unwrap().clone()?;
+        let base = app_dir.join("sounds");
+        let theme = self.get_sound_theme();
+        Some(if theme.is_empty() {
+            base
+        } else {
+            base.join(theme)
+        })
+    }
+
     // --- API Config ---
 
     pub fn get_api_config(&self) -> ApiConfig {
@@ -365,6 +869,115 @@ impl Store {
         None
     }
 
+    // --- Vocabulary ---
+
+    pub fn get_vocabulary(&self) -> Vocabulary {
+        self.data.lock().unwrap().settings.vocabulary.clone()
+    }
+
+    pub fn set_vocabulary(&self, vocabulary: Vocabulary) {
+        self.data.lock().unwrap().settings.vocabulary = vocabulary;
+        self.save();
+    }
+
+    /// Whisper `initial_prompt` / cloud `prompt` string biasing decoding toward the vocabulary,
+    /// or `None` when there are no terms to bias with.
+    pub fn vocabulary_prompt(&self) -> Option<String> {
+        let terms = self.data.lock().unwrap().settings.vocabulary.terms.clone();
+        let terms: Vec<String> = terms
+            .into_iter()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+        if terms.is_empty() {
+            None
+        } else {
+            Some(terms.join(", "))
+        }
+    }
+
+    /// Apply the case-insensitive whole-word replacement map to a finished transcript.
+    /// Longest phrases are applied first so multi-word entries win over single words.
+    pub fn apply_vocabulary_replacements(&self, text: &str) -> String {
+        let mut rules = self.data.lock().unwrap().settings.vocabulary.replacements.clone();
+        rules.retain(|r| !r.from.trim().is_empty());
+        // Longest-match-first: phrases with more words (then more chars) take precedence.
+        rules.sort_by(|a, b| {
+            let wa = a.from.split_whitespace().count();
+            let wb = b.from.split_whitespace().count();
+            wb.cmp(&wa).then(b.from.len().cmp(&a.from.len()))
+        });
+        let mut out = text.to_string();
+        for rule in &rules {
+            out = replace_whole_word_ci(&out, rule.from.trim(), &rule.to);
+        }
+        out
+    }
+
+    /// Canonical post-transcription text pipeline — the single place the two replacement layers
+    /// compose, so every entry point (the `transcribe` command and the hotkey release path) gets
+    /// the same deterministic result. Run in a fixed order:
+    ///
+    /// 1. Vocabulary replacements ([`Self::apply_vocabulary_replacements`]): the plain
+    ///    case-insensitive whole-word map users maintain alongside their prompt-biasing terms.
+    ///    Applied first so its exact spellings feed the dictionary clean input.
+    /// 2. Dictionary ([`Self::apply_dictionary`]): the richer subsystem — exact and blocked
+    ///    entries plus fuzzy/phonetic correction of near-miss tokens.
+    ///
+    /// The two maps stay separate because they solve different problems — vocabulary pairs with
+    /// the decoding prompt for terms the model should already know, while the dictionary repairs
+    /// mishearings after the fact — but callers should go through here rather than running either
+    /// pass alone.
+    pub fn apply_replacements(&self, text: &str) -> String {
+        let text = self.apply_vocabulary_replacements(text);
+        self.apply_dictionary(&text)
+    }
+
+    // --- OpenAI-compatible config ---
+
+    pub fn get_openai_config(&self) -> OpenAiConfig {
+        self.data.lock().unwrap().settings.openai_config.clone()
+    }
+
+    pub fn set_openai_config(&self, endpoint: String, api_key: String, model: String) {
+        let mut data = self.data.lock().unwrap();
+        data.settings.openai_config.endpoint = endpoint;
+        data.settings.openai_config.api_key = api_key;
+        if !model.trim().is_empty() {
+            data.settings.openai_config.model = model;
+        }
+        drop(data);
+        self.save();
+    }
+
+    /// Resolve the OpenAI-compatible endpoint from dashboard settings only.
+    pub fn resolve_openai_endpoint(&self) -> Option<String> {
+        let cfg = self.get_openai_config();
+        if !cfg.endpoint.is_empty() {
+            return Some(cfg.endpoint);
+        }
+        None
+    }
+
+    /// Resolve the OpenAI-compatible API key from dashboard settings only.
+    pub fn resolve_openai_api_key(&self) -> Option<String> {
+        let cfg = self.get_openai_config();
+        if !cfg.api_key.is_empty() {
+            return Some(cfg.api_key);
+        }
+        None
+    }
+
+    /// Resolve the OpenAI model name (defaults to `whisper-1`).
+    pub fn resolve_openai_model(&self) -> String {
+        let cfg = self.get_openai_config();
+        if cfg.model.trim().is_empty() {
+            "whisper-1".to_string()
+        } else {
+            cfg.model
+        }
+    }
+
     // --- Transcription config (Azure vs Local) ---
 
     pub fn get_transcription_config(&self) -> TranscriptionConfig {
@@ -376,27 +989,43 @@ impl Store {
         source: String,
         local_model_path: String,
         local_model_size: String,
+        language: Option<String>,
     ) {
         let mut data = self.data.lock().unwrap();
         data.settings.transcription.source = source;
         data.settings.transcription.local_model_path = local_model_path;
         data.settings.transcription.local_model_size = normalize_model_size(&local_model_size);
+        if let Some(lang) = language {
+            data.settings.transcription.language = normalize_language(&lang);
+        }
         drop(data);
         self.save();
     }
 
-    /// Default path for a given size (for UI preview). Does not use custom path.
-    pub fn get_default_local_model_path_for_size(&self, size: &str) -> Option<String> {
+    /// Default path for a given size (for UI preview). Does not use custom path. Uses the passed
+    /// `language` (or the configured one when `None`) so the preview shows the right artifact.
+    pub fn get_default_local_model_path_for_size(
+        &self,
+        size: &str,
+        language: Option<&str>,
+    ) -> Option<String> {
         let app_dir = self.app_data_dir.lock().unwrap().clone()?;
-        let name = model_filename_for_size(size);
+        let lang = language
+            .map(normalize_language)
+            .unwrap_or_else(|| self.get_transcription_config().language);
+        let name = model_filename_for_size(size, &lang);
         let p = app_dir.join("models").join(name);
         Some(p.to_string_lossy().to_string())
     }
 
-    /// Preferred transcription source: "azure" or "local".
+    /// Preferred transcription source: "azure", "local", or "openai".
     pub fn transcription_source(&self) -> String {
         let s = self.data.lock().unwrap().settings.transcription.source.clone();
-        if s == "local" { "local".to_string() } else { "azure".to_string() }
+        match s.as_str() {
+            "local" => "local".to_string(),
+            "openai" => "openai".to_string(),
+            _ => "azure".to_string(),
+        }
     }
 
     /// Path to the local Whisper model file. Uses custom path if set, else default under app data for current size.
@@ -407,7 +1036,7 @@ impl Store {
             return Some(p);
         }
         let app_dir = self.app_data_dir.lock().unwrap().clone()?;
-        let name = model_filename_for_size(&cfg.local_model_size);
+        let name = model_filename_for_size(&cfg.local_model_size, &cfg.language);
         let default_path = app_dir.join("models").join(name);
         Some(default_path)
     }
@@ -415,7 +1044,41 @@ impl Store {
     /// Default path where the app looks for the local model (for display in UI). Uses current size from config.
     pub fn get_default_local_model_path(&self) -> Option<String> {
         let cfg = self.get_transcription_config();
-        self.get_default_local_model_path_for_size(&cfg.local_model_size)
+        self.get_default_local_model_path_for_size(&cfg.local_model_size, Some(&cfg.language))
+    }
+
+    /// ggml filename for `size` under the currently configured language.
+    pub fn model_filename_for(&self, size: &str) -> String {
+        let lang = self.get_transcription_config().language;
+        model_filename_for_size(&normalize_model_size(size), &lang).to_string()
+    }
+
+    // --- Proxy ---
+
+    pub fn get_proxy_config(&self) -> ProxyConfig {
+        self.data.lock().unwrap().settings.proxy_config.clone()
+    }
+
+    pub fn set_proxy_config(&self, url: String) {
+        self.data.lock().unwrap().settings.proxy_config.url = url.trim().to_string();
+        self.save();
+    }
+
+    /// Proxy URL to use for downloads: the explicitly configured one if set, otherwise the first
+    /// of `HTTPS_PROXY` / `ALL_PROXY` present in the environment. `None` means connect directly.
+    pub fn resolve_proxy(&self) -> Option<String> {
+        let cfg = self.get_proxy_config();
+        if !cfg.url.is_empty() {
+            return Some(cfg.url);
+        }
+        for var in ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"] {
+            if let Ok(val) = std::env::var(var) {
+                if !val.is_empty() {
+                    return Some(val);
+                }
+            }
+        }
+        None
     }
 
     // --- License (product key) ---
@@ -429,20 +1092,63 @@ impl Store {
             .map(|d| d.join("license.json"))
     }
 
+    /// License server base URL: the configured one, or [`DEFAULT_LICENSE_ENDPOINT`].
+    pub fn resolve_license_endpoint(&self) -> String {
+        let cfg = self.get_api_config();
+        if cfg.license_endpoint.trim().is_empty() {
+            DEFAULT_LICENSE_ENDPOINT.to_string()
+        } else {
+            cfg.license_endpoint.trim().trim_end_matches('/').to_string()
+        }
+    }
+
+    /// Entitlement check with an offline grace period. Trusts the cached license while its expiry
+    /// is in the future; once expired, keeps returning `true` for [`LICENSE_GRACE_SECS`] past the
+    /// last successful server check — but only when that check predates the expiry, i.e. the server
+    /// never confirmed the expiry (the offline case). A successful check at or after expiry ends
+    /// access immediately. Kicks off a background re-validation at most once a day.
     pub fn get_license_status(&self) -> bool {
         let path = match self.license_path() {
             Ok(p) => p,
             Err(_) => return false,
         };
-        if !path.exists() {
-            return false;
-        }
-        if let Ok(contents) = fs::read_to_string(&path) {
-            if let Ok(_data) = serde_json::from_str::<LicenseData>(&contents) {
-                return true;
-            }
+        let data = match fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| serde_json::from_str::<LicenseData>(&c).ok())
+        {
+            Some(d) => d,
+            None => return false,
+        };
+
+        let now = unix_secs();
+        let expiry_ok = data.expires_at == 0 || now <= data.expires_at;
+        // Grace only covers the offline case: the last successful check predates the expiry, so the
+        // server never confirmed it lapsed. A check at or after expiry (which refreshes `last_check`)
+        // means the server has confirmed the expiry, so grace no longer applies.
+        let unconfirmed_expiry = data.expires_at != 0 && data.last_check < data.expires_at;
+        let grace_ok =
+            unconfirmed_expiry && now <= data.last_check.saturating_add(LICENSE_GRACE_SECS);
+
+        // At most one background re-validation per day; never blocks the status check. The last
+        // attempt is remembered in memory so an unreachable server is not retried on every call.
+        static LAST_ATTEMPT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+        let last_attempt = LAST_ATTEMPT.load(std::sync::atomic::Ordering::Relaxed);
+        if now >= data.last_check.saturating_add(LICENSE_RECHECK_SECS)
+            && now >= last_attempt.saturating_add(LICENSE_RECHECK_SECS)
+            && LAST_ATTEMPT
+                .compare_exchange(
+                    last_attempt,
+                    now,
+                    std::sync::atomic::Ordering::Relaxed,
+                    std::sync::atomic::Ordering::Relaxed,
+                )
+                .is_ok()
+        {
+            let endpoint = self.resolve_license_endpoint();
+            revalidate_license_in_background(endpoint, path, data.clone());
         }
-        false
+
+        expiry_ok || grace_ok
     }
 
     pub fn activate_license(&self, key: &str) -> Result<(), String> {
@@ -451,11 +1157,19 @@ impl Store {
         if let Some(parent) = path.parent() {
             let _ = fs::create_dir_all(parent);
         }
-        let activated_at = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-        let data = LicenseData { activated_at };
+        let machine_id = machine_id(&path);
+        let endpoint = self.resolve_license_endpoint();
+        let resp = post_license(&format!("{}/activate", endpoint), key, &machine_id)?;
+
+        let now = unix_secs();
+        let data = LicenseData {
+            activated_at: now,
+            issued_at: if resp.issued_at == 0 { now } else { resp.issued_at },
+            expires_at: resp.expires_at,
+            machine_id,
+            signature: resp.signature,
+            last_check: now,
+        };
         let json = serde_json::to_string_pretty(&data).map_err(|e| e.to_string())?;
         fs::write(&path, json).map_err(|e| e.to_string())?;
         Ok(())
@@ -470,6 +1184,128 @@ impl Store {
     }
 }
 
+/// Case-insensitive whole-word replacement of `from` with `to` (word boundaries = non-alphanumerics).
+fn replace_whole_word_ci(haystack: &str, from: &str, to: &str) -> String {
+    if from.is_empty() {
+        return haystack.to_string();
+    }
+    let hay_lower = haystack.to_lowercase();
+    let needle = from.to_lowercase();
+    let bytes = haystack.as_bytes();
+    let mut out = String::with_capacity(haystack.len());
+    let mut i = 0;
+    while i < haystack.len() {
+        if hay_lower[i..].starts_with(&needle) {
+            let before_ok = i == 0 || !bytes[i - 1].is_ascii_alphanumeric();
+            let end = i + needle.len();
+            let after_ok = end >= bytes.len() || !bytes[end].is_ascii_alphanumeric();
+            if before_ok && after_ok {
+                out.push_str(to);
+                i = end;
+                continue;
+            }
+        }
+        let ch = haystack[i..].chars().next().unwrap();
+        out.push(ch);
+        i += ch.len_utf8();
+    }
+    out
+}
+
+/// Carry the casing of the matched text onto `replacement`: ALL CAPS stays all caps, a leading
+/// capital stays capitalized, everything else is left as authored.
+fn match_replacement_case(matched: &str, replacement: &str) -> String {
+    let letters: Vec<char> = matched.chars().filter(|c| c.is_alphabetic()).collect();
+    if !letters.is_empty() && letters.iter().all(|c| c.is_uppercase()) {
+        return replacement.to_uppercase();
+    }
+    if matched.chars().next().is_some_and(|c| c.is_uppercase()) {
+        let mut chars = replacement.chars();
+        if let Some(first) = chars.next() {
+            return first.to_uppercase().collect::<String>() + chars.as_str();
+        }
+    }
+    replacement.to_string()
+}
+
+/// Byte `(start, end)` spans of each maximal alphanumeric run in `text`.
+fn word_spans(text: &str) -> Vec<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_alphanumeric() {
+            let start = i;
+            while i < bytes.len() && bytes[i].is_ascii_alphanumeric() {
+                i += 1;
+            }
+            spans.push((start, i));
+        } else {
+            i += 1;
+        }
+    }
+    spans
+}
+
+/// Case-insensitive Levenshtein distance normalized by the longer string's length (0.0 = equal,
+/// 1.0 = completely different). Whitespace in each input is collapsed so "Cooper Netties" and
+/// "coopernetties" compare the same.
+fn normalized_edit_distance(a: &str, b: &str) -> f32 {
+    let norm = |s: &str| -> Vec<char> {
+        s.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect()
+    };
+    let a = norm(a);
+    let b = norm(b);
+    if a.is_empty() && b.is_empty() {
+        return 0.0;
+    }
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    let dist = prev[b.len()];
+    dist as f32 / a.len().max(b.len()) as f32
+}
+
+/// Compact phonetic key approximating Double Metaphone: used only as a tie-break so homophone-ish
+/// mis-hearings collapse to the same code. Lower-cases, drops vowels after the first letter,
+/// folds common equivalent consonants, and squeezes runs.
+fn phonetic_key(s: &str) -> String {
+    let cleaned: String = s
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect();
+    let mut out = String::with_capacity(cleaned.len());
+    for (i, c) in cleaned.chars().enumerate() {
+        let folded = match c {
+            'b' | 'p' | 'f' | 'v' => 'b',
+            'c' | 'k' | 'g' | 'q' | 'j' => 'k',
+            'd' | 't' => 't',
+            's' | 'z' | 'x' => 's',
+            'm' | 'n' => 'n',
+            'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'h' | 'w' => {
+                if i == 0 {
+                    c
+                } else {
+                    continue;
+                }
+            }
+            other => other,
+        };
+        if out.chars().last() != Some(folded) {
+            out.push(folded);
+        }
+    }
+    out
+}
+
 fn normalize_model_size(s: &str) -> String {
     let t = s.trim().to_lowercase();
     match t.as_str() {
@@ -477,3 +1313,15 @@ fn normalize_model_size(s: &str) -> String {
         _ => "tiny".to_string(),
     }
 }
+
+/// Validate a transcription language: "auto", a 2–3 letter ISO code, or fall back to English.
+fn normalize_language(s: &str) -> String {
+    let t = s.trim().to_lowercase();
+    if t == "auto" {
+        return t;
+    }
+    if (2..=3).contains(&t.len()) && t.chars().all(|c| c.is_ascii_alphabetic()) {
+        return t;
+    }
+    "en".to_string()
+}
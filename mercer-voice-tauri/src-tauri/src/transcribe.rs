@@ -79,7 +79,12 @@ fn resolve_credential(
 }
 
 /// Run transcription with an already-loaded context (no model load). Used by the dedicated thread.
-fn transcribe_with_context(ctx: &WhisperContext, wav_path: &str) -> Result<String, String> {
+fn transcribe_with_context(
+    ctx: &WhisperContext,
+    wav_path: &str,
+    initial_prompt: Option<&str>,
+    trim: bool,
+) -> Result<String, String> {
     let reader = WavReader::open(wav_path).map_err(|e| format!("Failed to open WAV: {}", e))?;
     let samples_i16: Vec<i16> = reader
         .into_samples::<i16>()
@@ -90,10 +95,40 @@ fn transcribe_with_context(ctx: &WhisperContext, wav_path: &str) -> Result<Strin
         return Err("Audio file too short, likely no speech captured".to_string());
     }
 
+    // Gate on voice activity before touching Whisper: a capture with no qualifying speech
+    // segment returns empty immediately rather than producing a silence hallucination.
+    let samples_i16 = match crate::vad::trim_silence(&samples_i16, trim) {
+        Some(trimmed) => trimmed,
+        None => {
+            eprintln!("[Verba] VAD: no speech detected, skipping transcription");
+            return Ok(String::new());
+        }
+    };
+
     let mut audio_f32 = vec![0.0f32; samples_i16.len()];
     convert_integer_to_float_audio(&samples_i16, &mut audio_f32)
         .map_err(|e| format!("Whisper conversion error: {:?}", e))?;
 
+    let text = run_whisper_on_samples(ctx, &audio_f32, initial_prompt)?;
+
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return Err("Local Whisper returned no text".to_string());
+    }
+    if is_likely_hallucination(trimmed) {
+        return Ok(String::new());
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Run Whisper over already-decoded f32 samples (16 kHz mono) and return the raw joined text.
+/// Shared by the one-shot WAV path and the streaming transcriber, which feeds overlapping
+/// windows of `f32` samples directly rather than re-reading a WAV each pass.
+fn run_whisper_on_samples(
+    ctx: &WhisperContext,
+    audio_f32: &[f32],
+    initial_prompt: Option<&str>,
+) -> Result<String, String> {
     let mut state = ctx.create_state().map_err(|e| format!("Failed to create Whisper state: {:?}", e))?;
 
     let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
@@ -101,6 +136,11 @@ fn transcribe_with_context(ctx: &WhisperContext, wav_path: &str) -> Result<Strin
     params.set_print_realtime(false);
     params.set_print_timestamps(false);
     params.set_language(Some("en"));
+    if let Some(prompt) = initial_prompt {
+        if !prompt.is_empty() {
+            params.set_initial_prompt(prompt);
+        }
+    }
     let n_threads = std::thread::available_parallelism()
         .map(|p| p.get())
         .unwrap_or(1)
@@ -108,7 +148,7 @@ fn transcribe_with_context(ctx: &WhisperContext, wav_path: &str) -> Result<Strin
     params.set_n_threads(n_threads);
 
     state
-        .full(params, &audio_f32)
+        .full(params, audio_f32)
         .map_err(|e| format!("Whisper transcription failed: {:?}", e))?;
 
     let mut text = String::new();
@@ -117,22 +157,320 @@ fn transcribe_with_context(ctx: &WhisperContext, wav_path: &str) -> Result<Strin
             text.push_str(s);
         }
     }
+    Ok(text)
+}
 
-    let trimmed = text.trim();
-    if trimmed.is_empty() {
-        return Err("Local Whisper returned no text".to_string());
+// ===== STRUCTURED OUTPUT =====
+
+/// One timestamped transcript segment. Mirrors the `start_time`/`end_time`/`stable` item model
+/// used by streaming transcribers, so the same shape serves both subtitle export and later editing.
+#[derive(Clone, serde::Serialize)]
+pub struct TranscriptSegment {
+    pub text: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    /// False while the segment is still a partial streaming hypothesis; true once committed.
+    pub stable: bool,
+}
+
+/// Transcribe into timestamped segments using Whisper's per-segment timestamps (centiseconds).
+fn transcribe_structured_with_context(
+    ctx: &WhisperContext,
+    audio_f32: &[f32],
+) -> Result<Vec<TranscriptSegment>, String> {
+    let mut state = ctx.create_state().map_err(|e| format!("Failed to create Whisper state: {:?}", e))?;
+
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_timestamps(false);
+    params.set_token_timestamps(true);
+    params.set_language(Some("en"));
+    let n_threads = std::thread::available_parallelism()
+        .map(|p| p.get())
+        .unwrap_or(1)
+        .max(1) as i32;
+    params.set_n_threads(n_threads);
+
+    state
+        .full(params, audio_f32)
+        .map_err(|e| format!("Whisper transcription failed: {:?}", e))?;
+
+    let mut segments = Vec::new();
+    for segment in state.as_iter() {
+        let Ok(text) = segment.to_str() else { continue };
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        // whisper.cpp timestamps are in centiseconds (1/100 s).
+        segments.push(TranscriptSegment {
+            text: text.to_string(),
+            start_ms: segment.start_timestamp() * 10,
+            end_ms: segment.end_timestamp() * 10,
+            stable: true,
+        });
     }
-    if is_likely_hallucination(trimmed) {
-        return Ok(String::new());
+    Ok(segments)
+}
+
+/// Render segments as SubRip (`.srt`): sequential 1-based cues, `HH:MM:SS,mmm` timestamps.
+pub fn to_srt(segments: &[TranscriptSegment]) -> String {
+    let mut out = String::new();
+    for (i, seg) in segments.iter().enumerate() {
+        out.push_str(&format!("{}\n", i + 1));
+        out.push_str(&format!(
+            "{} --> {}\n",
+            format_timestamp(seg.start_ms, ','),
+            format_timestamp(seg.end_ms, ',')
+        ));
+        out.push_str(seg.text.trim());
+        out.push_str("\n\n");
     }
-    Ok(trimmed.to_string())
+    out
+}
+
+/// Render segments as WebVTT (`.vtt`): a `WEBVTT` header then `HH:MM:SS.mmm` cues.
+pub fn to_vtt(segments: &[TranscriptSegment]) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for seg in segments {
+        out.push_str(&format!(
+            "{} --> {}\n",
+            format_timestamp(seg.start_ms, '.'),
+            format_timestamp(seg.end_ms, '.')
+        ));
+        out.push_str(seg.text.trim());
+        out.push_str("\n\n");
+    }
+    out
 }
 
-/// Request for the dedicated local transcription thread: (wav_path, model_path, reply_sender).
-type LocalRequest = (String, PathBuf, mpsc::Sender<Result<String, String>>);
+/// Format milliseconds as `HH:MM:SS<sep>mmm` (comma for SRT, dot for VTT).
+fn format_timestamp(ms: i64, sep: char) -> String {
+    let ms = ms.max(0);
+    let h = ms / 3_600_000;
+    let m = (ms % 3_600_000) / 60_000;
+    let s = (ms % 60_000) / 1000;
+    let millis = ms % 1000;
+    format!("{:02}:{:02}:{:02}{}{:03}", h, m, s, sep, millis)
+}
+
+// ===== STREAMING TRANSCRIPTION =====
+
+/// Emitted to the overlay while recording: `stable` text is committed and will not change,
+/// `partial` is the tail hypothesis that may still be revised on the next pass.
+#[derive(Clone, serde::Serialize)]
+pub struct StreamingUpdate {
+    /// The novel stable suffix promoted since the previous update (never re-emits committed text).
+    pub stable: String,
+    /// The current unstable tail (the part still allowed to change).
+    pub partial: String,
+}
 
-fn get_local_transcription_tx() -> &'static mpsc::Sender<LocalRequest> {
-    static TX: OnceLock<mpsc::Sender<LocalRequest>> = OnceLock::new();
+/// Rolling-window streaming transcriber. Runs one Whisper pass over an overlapping window each
+/// time captured audio is pushed (see `poll`; there is no separate timer) and promotes leading tokens to "committed" once they survive `STABLE_PASSES` consecutive passes,
+/// borrowing the result-stability idea from streaming speech APIs.
+pub struct StreamingTranscriber {
+    /// All samples captured so far (16 kHz mono f32).
+    buffer: Vec<f32>,
+    /// Number of committed samples at the front of `buffer` (window never shifts past this).
+    committed_samples: usize,
+    /// Every token promoted to stable so far, in order. Joined with spaces for the final transcript.
+    committed_tokens: Vec<String>,
+    /// Last hypothesis over the uncommitted region and how many passes its leading tokens held.
+    last_tokens: Vec<String>,
+    stable_run: usize,
+}
+
+/// Append `incoming` to `committed`, dropping the longest leading run of `incoming` that already
+/// appears as the trailing run of `committed` (the window's backward overlap re-transcribes audio
+/// whose tokens are already committed). Returns the joined text of what was actually appended.
+fn merge_append(committed: &mut Vec<String>, incoming: &[String]) -> String {
+    let max_overlap = committed.len().min(incoming.len());
+    let mut overlap = 0;
+    for k in (1..=max_overlap).rev() {
+        if committed[committed.len() - k..] == incoming[..k] {
+            overlap = k;
+            break;
+        }
+    }
+    let added = &incoming[overlap..];
+    committed.extend_from_slice(added);
+    added.join(" ")
+}
+
+/// Window length fed to Whisper each pass (~5 s at 16 kHz).
+const STREAM_WINDOW_SAMPLES: usize = 16_000 * 5;
+/// How much committed audio to keep as context ahead of the window (~1 s).
+const STREAM_OVERLAP_SAMPLES: usize = 16_000;
+/// Consecutive passes a leading token must survive unchanged before it is committed.
+const STABLE_PASSES: usize = 2;
+
+impl Default for StreamingTranscriber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamingTranscriber {
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            committed_samples: 0,
+            committed_tokens: Vec::new(),
+            last_tokens: Vec::new(),
+            stable_run: 0,
+        }
+    }
+
+    /// Append freshly captured samples to the rolling buffer.
+    pub fn push_samples(&mut self, new: &[f32]) {
+        self.buffer.extend_from_slice(new);
+    }
+
+    /// Run one incremental pass. Returns `Some(update)` when there is new text to show.
+    pub fn poll(&mut self, ctx: &WhisperContext) -> Option<StreamingUpdate> {
+        // Window = a slice of context before the committed edge through the end of the buffer.
+        let region_start = self.committed_samples.saturating_sub(STREAM_OVERLAP_SAMPLES);
+        let region = &self.buffer[region_start..];
+        if region.len() < STREAM_WINDOW_SAMPLES.min(16_000) {
+            return None;
+        }
+        // Cap the window so a long dictation doesn't grow the pass cost without bound. Track its
+        // absolute start so promotions can advance `committed_samples` proportionally.
+        let (window, window_start) = if region.len() > STREAM_WINDOW_SAMPLES {
+            (
+                &region[region.len() - STREAM_WINDOW_SAMPLES..],
+                self.buffer.len() - STREAM_WINDOW_SAMPLES,
+            )
+        } else {
+            (region, region_start)
+        };
+        let window_len = window.len();
+
+        let text = run_whisper_on_samples(ctx, window, None).ok()?;
+        let tokens: Vec<String> = text.split_whitespace().map(str::to_string).collect();
+        if tokens.is_empty() {
+            return None;
+        }
+
+        // How many leading tokens match the previous hypothesis?
+        let matching = tokens
+            .iter()
+            .zip(self.last_tokens.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        if matching > 0 && matching == self.last_tokens.len().min(tokens.len()) {
+            self.stable_run += 1;
+        } else {
+            self.stable_run = 1;
+        }
+        self.last_tokens = tokens.clone();
+
+        if self.stable_run < STABLE_PASSES {
+            return Some(StreamingUpdate {
+                stable: String::new(),
+                partial: tokens.join(" "),
+            });
+        }
+
+        // Promote only the leading tokens that matched the previous pass, never the last couple of
+        // tokens (the tail stays partial), appending only the genuinely new tokens to the running
+        // transcript — never overwriting earlier commits.
+        let keep_partial = 2.min(tokens.len());
+        let promote_len = (tokens.len() - keep_partial).min(matching);
+        let promote = &tokens[..promote_len];
+        let suffix = merge_append(&mut self.committed_tokens, promote);
+        // Advance the committed edge proportionally to the promoted fraction of the window, so the
+        // still-partial tail stays in the buffer (we never skip audio whose text isn't captured yet).
+        let promoted_frac = promote.len() as f64 / tokens.len() as f64;
+        let advance = (window_len as f64 * promoted_frac) as usize;
+        self.committed_samples = (window_start + advance)
+            .min(self.buffer.len())
+            .max(self.committed_samples);
+        self.stable_run = 0;
+        self.last_tokens.clear();
+
+        Some(StreamingUpdate {
+            stable: suffix,
+            partial: tokens[promote_len..].join(" "),
+        })
+    }
+
+    /// Final pass over the uncommitted tail on release; reconciles and returns the full text.
+    pub fn finalize(&mut self, ctx: &WhisperContext) -> Result<String, String> {
+        let tail_start = self.committed_samples.saturating_sub(STREAM_OVERLAP_SAMPLES);
+        let tail = &self.buffer[tail_start..];
+        if tail.len() >= 1600 {
+            let tail_text = run_whisper_on_samples(ctx, tail, None)?;
+            let tail_tokens: Vec<String> =
+                tail_text.split_whitespace().map(str::to_string).collect();
+            // Merge rather than concatenate: the tail window overlaps already-committed audio.
+            merge_append(&mut self.committed_tokens, &tail_tokens);
+        }
+        let full = self.committed_tokens.join(" ");
+        let trimmed = full.trim();
+        if trimmed.is_empty() || is_likely_hallucination(trimmed) {
+            return Ok(String::new());
+        }
+        Ok(trimmed.to_string())
+    }
+}
+
+/// Reply channel for a local job: plain joined text, or timestamped segments.
+enum LocalReply {
+    Plain(mpsc::Sender<Result<String, String>>),
+    Structured(mpsc::Sender<Result<Vec<TranscriptSegment>, String>>),
+}
+
+/// One captured window (or end-of-stream marker) fed to a live streaming session.
+enum StreamInput {
+    /// A fresh 16 kHz mono window, already converted to float.
+    Samples(Vec<f32>),
+    /// No more audio — run the final pass and return the reconciled transcript.
+    Finish,
+}
+
+/// Work for the dedicated local-model thread, which owns the loaded `WhisperContext`.
+enum LocalJob {
+    /// Transcribe a finished WAV in one shot.
+    OneShot {
+        wav_path: String,
+        model_path: PathBuf,
+        initial_prompt: Option<String>,
+        /// The user's silence-trimming setting, captured per request.
+        trim: bool,
+        reply: LocalReply,
+    },
+    /// Drive a live `StreamingTranscriber` from captured windows until `StreamInput::Finish`.
+    Stream {
+        model_path: PathBuf,
+        input: mpsc::Receiver<StreamInput>,
+        on_update: Box<dyn FnMut(StreamingUpdate) + Send>,
+        reply: mpsc::Sender<Result<String, String>>,
+    },
+}
+
+impl LocalJob {
+    fn model_path(&self) -> &PathBuf {
+        match self {
+            LocalJob::OneShot { model_path, .. } | LocalJob::Stream { model_path, .. } => model_path,
+        }
+    }
+
+    /// Report a load/setup failure to whichever reply channel this job carries.
+    fn fail(self, err: String) {
+        match self {
+            LocalJob::OneShot { reply, .. } => reply.send_err(err),
+            LocalJob::Stream { reply, .. } => {
+                let _ = reply.send(Err(err));
+            }
+        }
+    }
+}
+
+fn get_local_transcription_tx() -> &'static mpsc::Sender<LocalJob> {
+    static TX: OnceLock<mpsc::Sender<LocalJob>> = OnceLock::new();
     TX.get_or_init(|| {
         let (tx, rx) = mpsc::channel();
         std::thread::spawn(move || run_local_transcription_loop(rx));
@@ -141,18 +479,20 @@ fn get_local_transcription_tx() -> &'static mpsc::Sender<LocalRequest> {
 }
 
 /// Dedicated thread: load model once (or when path changes), reuse context for every request.
-fn run_local_transcription_loop(rx: mpsc::Receiver<LocalRequest>) {
+fn run_local_transcription_loop(rx: mpsc::Receiver<LocalJob>) {
     let mut cached_path: Option<PathBuf> = None;
     let mut cached_ctx: Option<WhisperContext> = None;
 
-    while let Ok((wav_path, model_path, reply_tx)) = rx.recv() {
+    while let Ok(job) = rx.recv() {
+        let model_path = job.model_path().clone();
         let need_load = cached_path.as_ref() != Some(&model_path);
         if need_load {
             if !model_path.exists() {
-                let _ = reply_tx.send(Err(format!(
+                let err = format!(
                     "Local model not found at {}. In Settings → Transcription, click \"Download model\" for the selected size, or choose a smaller size (e.g. Tiny) that you've already downloaded.",
                     model_path.display()
-                )));
+                );
+                job.fail(err);
                 continue;
             }
             eprintln!("[Verba] Loading local Whisper model (one-time per session): {}", model_path.display());
@@ -164,7 +504,7 @@ fn run_local_transcription_loop(rx: mpsc::Receiver<LocalRequest>) {
                     cached_path = Some(model_path);
                 }
                 Err(e) => {
-                    let _ = reply_tx.send(Err(format!("Failed to load model: {:?}", e)));
+                    job.fail(format!("Failed to load model: {:?}", e));
                     continue;
                 }
             }
@@ -172,16 +512,164 @@ fn run_local_transcription_loop(rx: mpsc::Receiver<LocalRequest>) {
 
         eprintln!("[Verba] Transcription: LOCAL MODEL ONLY — no data is sent to Azure or any cloud service.");
         let ctx = cached_ctx.as_ref().unwrap();
-        let result = transcribe_with_context(ctx, &wav_path);
-        let _ = reply_tx.send(result);
+        match job {
+            LocalJob::OneShot { wav_path, initial_prompt, trim, reply, .. } => match reply {
+                LocalReply::Plain(tx) => {
+                    let _ = tx.send(transcribe_with_context(ctx, &wav_path, initial_prompt.as_deref(), trim));
+                }
+                LocalReply::Structured(tx) => {
+                    let _ = tx.send(transcribe_structured_from_wav(ctx, &wav_path, trim));
+                }
+            },
+            LocalJob::Stream { input, mut on_update, reply, .. } => {
+                let mut streamer = StreamingTranscriber::new();
+                while let Ok(msg) = input.recv() {
+                    match msg {
+                        StreamInput::Samples(window) => {
+                            streamer.push_samples(&window);
+                            if let Some(update) = streamer.poll(ctx) {
+                                on_update(update);
+                            }
+                        }
+                        StreamInput::Finish => break,
+                    }
+                }
+                let _ = reply.send(streamer.finalize(ctx));
+            }
+        }
+    }
+}
+
+impl LocalReply {
+    fn send_err(self, err: String) {
+        match self {
+            LocalReply::Plain(tx) => {
+                let _ = tx.send(Err(err));
+            }
+            LocalReply::Structured(tx) => {
+                let _ = tx.send(Err(err));
+            }
+        }
+    }
+}
+
+/// Cloneable audio sink for a streaming session; hand one to whatever produces captured windows
+/// (the hotkey worker forwards `audio-chunk` events through it). Pushing after [`StreamHandle::finish`]
+/// is a no-op.
+#[derive(Clone)]
+pub struct StreamFeeder {
+    input: mpsc::Sender<StreamInput>,
+}
+
+impl StreamFeeder {
+    /// Hand one captured 16 kHz mono window to the streaming transcriber.
+    pub fn push(&self, samples: &[i16]) {
+        let mut window = vec![0.0f32; samples.len()];
+        if convert_integer_to_float_audio(samples, &mut window).is_ok() {
+            let _ = self.input.send(StreamInput::Samples(window));
+        }
     }
 }
 
+/// Owner handle for a live local streaming session. Obtain a [`StreamFeeder`] to push audio, then
+/// call [`StreamHandle::finish`] to run the final pass and get the reconciled transcript. Only the
+/// on-device model streams; cloud backends transcribe on stop.
+pub struct StreamHandle {
+    input: mpsc::Sender<StreamInput>,
+    reply: mpsc::Receiver<Result<String, String>>,
+}
+
+impl StreamHandle {
+    /// A cloneable sink for feeding captured windows into this session.
+    pub fn feeder(&self) -> StreamFeeder {
+        StreamFeeder {
+            input: self.input.clone(),
+        }
+    }
+
+    /// Signal end of audio and block for the reconciled final transcript.
+    pub fn finish(self) -> Result<String, String> {
+        let _ = self.input.send(StreamInput::Finish);
+        self.reply
+            .recv()
+            .map_err(|_| "No response from streaming thread".to_string())?
+    }
+}
+
+/// Start a live streaming session on the dedicated local-model thread. Partial/stable updates are
+/// delivered through `on_update` as they are produced; the caller keeps the returned handle to feed
+/// audio and finalize on stop.
+pub fn start_local_stream(
+    model_path: PathBuf,
+    on_update: impl FnMut(StreamingUpdate) + Send + 'static,
+) -> StreamHandle {
+    let (input_tx, input_rx) = mpsc::channel();
+    let (reply_tx, reply_rx) = mpsc::channel();
+    let _ = get_local_transcription_tx().send(LocalJob::Stream {
+        model_path,
+        input: input_rx,
+        on_update: Box::new(on_update),
+        reply: reply_tx,
+    });
+    StreamHandle {
+        input: input_tx,
+        reply: reply_rx,
+    }
+}
+
+/// Read a WAV, gate on VAD, and return timestamped segments.
+fn transcribe_structured_from_wav(
+    ctx: &WhisperContext,
+    wav_path: &str,
+    trim: bool,
+) -> Result<Vec<TranscriptSegment>, String> {
+    let reader = WavReader::open(wav_path).map_err(|e| format!("Failed to open WAV: {}", e))?;
+    let samples_i16: Vec<i16> = reader.into_samples::<i16>().filter_map(Result::ok).collect();
+    let samples_i16 = match crate::vad::trim_silence(&samples_i16, trim) {
+        Some(trimmed) => trimmed,
+        None => return Ok(Vec::new()),
+    };
+    let mut audio_f32 = vec![0.0f32; samples_i16.len()];
+    convert_integer_to_float_audio(&samples_i16, &mut audio_f32)
+        .map_err(|e| format!("Whisper conversion error: {:?}", e))?;
+    transcribe_structured_with_context(ctx, &audio_f32)
+}
+
 /// Run local Whisper (dispatches to dedicated thread so model is loaded once and reused).
-fn transcribe_local_sync(wav_path: String, model_path: PathBuf) -> Result<String, String> {
+fn transcribe_local_sync(
+    wav_path: String,
+    model_path: PathBuf,
+    initial_prompt: Option<String>,
+    trim: bool,
+) -> Result<String, String> {
+    let (reply_tx, reply_rx) = mpsc::channel();
+    get_local_transcription_tx()
+        .send(LocalJob::OneShot {
+            wav_path,
+            model_path,
+            initial_prompt,
+            trim,
+            reply: LocalReply::Plain(reply_tx),
+        })
+        .map_err(|_| "Local transcription thread closed".to_string())?;
+    reply_rx.recv().map_err(|_| "No response from transcription thread".to_string())?
+}
+
+/// Like `transcribe_local_sync` but returns timestamped segments for subtitle export.
+fn transcribe_structured_local_sync(
+    wav_path: String,
+    model_path: PathBuf,
+    trim: bool,
+) -> Result<Vec<TranscriptSegment>, String> {
     let (reply_tx, reply_rx) = mpsc::channel();
     get_local_transcription_tx()
-        .send((wav_path, model_path, reply_tx))
+        .send(LocalJob::OneShot {
+            wav_path,
+            model_path,
+            initial_prompt: None,
+            trim,
+            reply: LocalReply::Structured(reply_tx),
+        })
         .map_err(|_| "Local transcription thread closed".to_string())?;
     reply_rx.recv().map_err(|_| "No response from transcription thread".to_string())?
 }
@@ -194,17 +682,132 @@ pub async fn transcribe_impl(
     endpoint: Option<String>,
     api_key: Option<String>,
     local_model_path: Option<std::path::PathBuf>,
+    openai: Option<OpenAiCredentials>,
+    initial_prompt: Option<String>,
+    trim: bool,
+) -> Result<String, String> {
+    match source.as_str() {
+        "local" => {
+            eprintln!("[Verba] Using on-device model only. No data will be sent to Azure.");
+            let path = local_model_path.ok_or_else(|| "Local model path not set".to_string())?;
+            tokio::task::spawn_blocking(move || transcribe_local_sync(wav_path, path, initial_prompt, trim))
+                .await
+                .map_err(|e| format!("Local transcription task failed: {}", e))?
+        }
+        "openai" => {
+            eprintln!("[Verba] Using OpenAI-compatible endpoint. No on-device model is used.");
+            transcribe_openai(wav_path, openai, initial_prompt, trim).await
+        }
+        _ => {
+            eprintln!("[Verba] Using Azure Whisper. No on-device model is used; audio is sent to your Azure endpoint.");
+            transcribe_azure(wav_path, endpoint, api_key, initial_prompt, trim).await
+        }
+    }
+}
+
+/// Endpoint URL, bearer key, and model name for the OpenAI-compatible backend.
+pub struct OpenAiCredentials {
+    pub endpoint: Option<String>,
+    pub api_key: Option<String>,
+    pub model: String,
+}
+
+/// OpenAI-compatible `/v1/audio/transcriptions` backend. Reuses the pooled `client()` and the
+/// same 429 backoff loop as Azure, but authenticates with `Authorization: Bearer` and sends the
+/// `model`/`response_format` form fields the OpenAI API expects.
+async fn transcribe_openai(
+    wav_path: String,
+    creds: Option<OpenAiCredentials>,
+    initial_prompt: Option<String>,
+    trim: bool,
 ) -> Result<String, String> {
-    if source == "local" {
-        eprintln!("[Verba] Using on-device model only. No data will be sent to Azure.");
-        let path = local_model_path.ok_or_else(|| "Local model path not set".to_string())?;
-        tokio::task::spawn_blocking(move || transcribe_local_sync(wav_path, path))
+    eprintln!("[Verba] Transcription: OPENAI-COMPATIBLE ONLY — on-device model is not used.");
+    let creds = creds.ok_or_else(|| {
+        "OpenAI endpoint not set — enter the endpoint URL, API key, and model in Settings → Transcription (OpenAI section).".to_string()
+    })?;
+    let endpoint = resolve_credential(creds.endpoint, "OPENAI_ENDPOINT", None)?;
+    let api_key = resolve_credential(creds.api_key, "OPENAI_API_KEY", None)?;
+
+    // Gate on voice activity before spending an API round trip on silence.
+    if let Ok(reader) = WavReader::open(&wav_path) {
+        let samples_i16: Vec<i16> = reader.into_samples::<i16>().filter_map(Result::ok).collect();
+        if crate::vad::speech_bounds(&samples_i16, trim).is_none() {
+            eprintln!("[Verba] VAD: no speech detected, skipping OpenAI upload");
+            return Ok(String::new());
+        }
+    }
+
+    let file_bytes = tokio::fs::read(&wav_path)
+        .await
+        .map_err(|e| format!("Failed to read WAV file: {}", e))?;
+    if file_bytes.len() < 1000 {
+        return Err("Audio file too small, likely no speech captured".to_string());
+    }
+
+    let max_retries = 3;
+    for attempt in 0..=max_retries {
+        let file_part = multipart::Part::bytes(file_bytes.clone())
+            .file_name("recording.wav")
+            .mime_str("audio/wav")
+            .map_err(|e| format!("Failed to create multipart: {}", e))?;
+        let mut form = multipart::Form::new()
+            .part("file", file_part)
+            .text("model", creds.model.clone())
+            .text("response_format", "json");
+        if let Some(ref prompt) = initial_prompt {
+            if !prompt.is_empty() {
+                form = form.text("prompt", prompt.clone());
+            }
+        }
+
+        let response = client()
+            .post(&endpoint)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .multipart(form)
+            .send()
             .await
-            .map_err(|e| format!("Local transcription task failed: {}", e))?
-    } else {
-        eprintln!("[Verba] Using Azure Whisper. No on-device model is used; audio is sent to your Azure endpoint.");
-        transcribe_azure(wav_path, endpoint, api_key).await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        let status = response.status();
+
+        if status.as_u16() == 429 && attempt < max_retries {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(2u64.pow(attempt as u32 + 1));
+            eprintln!("Rate limited. Retrying in {}s (attempt {}/{})", retry_after, attempt + 1, max_retries);
+            tokio::time::sleep(Duration::from_secs(retry_after)).await;
+            continue;
+        }
+
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(format!("OpenAI API error {}: {}", status, error_body));
+        }
+
+        let body = response.text().await.map_err(|e| format!("Failed to read response: {}", e))?;
+        if body.trim().is_empty() {
+            return Err("OpenAI API returned an empty response".to_string());
+        }
+
+        let json: Value = serde_json::from_str(&body).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+        if let Some(text) = json.get("text").and_then(|t| t.as_str()) {
+            let trimmed = text.trim();
+            if trimmed.is_empty() {
+                return Err("OpenAI returned empty text".to_string());
+            }
+            let out = trimmed.to_string();
+            if is_likely_hallucination(&out) {
+                return Ok(String::new());
+            }
+            return Ok(out);
+        }
+        return Err("No 'text' field in OpenAI response".to_string());
     }
+
+    Err("Max retries exceeded".to_string())
 }
 
 /// Azure cloud transcription only. No on-device/local model is loaded or used.
@@ -212,11 +815,22 @@ async fn transcribe_azure(
     wav_path: String,
     pre_endpoint: Option<String>,
     pre_api_key: Option<String>,
+    initial_prompt: Option<String>,
+    trim: bool,
 ) -> Result<String, String> {
     eprintln!("[Verba] Transcription: AZURE ONLY — on-device model is not used.");
     let endpoint = resolve_credential(pre_endpoint, "AZURE_WHISPER_ENDPOINT", None)?;
     let api_key = resolve_credential(pre_api_key, "AZURE_WHISPER_API_KEY", None)?;
 
+    // Gate on voice activity before spending an API round trip on silence.
+    if let Ok(reader) = WavReader::open(&wav_path) {
+        let samples_i16: Vec<i16> = reader.into_samples::<i16>().filter_map(Result::ok).collect();
+        if crate::vad::speech_bounds(&samples_i16, trim).is_none() {
+            eprintln!("[Verba] VAD: no speech detected, skipping Azure upload");
+            return Ok(String::new());
+        }
+    }
+
     let file_bytes = tokio::fs::read(&wav_path)
         .await
         .map_err(|e| format!("Failed to read WAV file: {}", e))?;
@@ -235,7 +849,12 @@ async fn transcribe_azure(
             .mime_str("audio/wav")
             .map_err(|e| format!("Failed to create multipart: {}", e))?;
 
-        let form = multipart::Form::new().part("file", file_part);
+        let mut form = multipart::Form::new().part("file", file_part);
+        if let Some(ref prompt) = initial_prompt {
+            if !prompt.is_empty() {
+                form = form.text("prompt", prompt.clone());
+            }
+        }
 
         let response = client()
             .post(&endpoint)
@@ -287,11 +906,62 @@ async fn transcribe_azure(
     Err("Max retries exceeded".to_string())
 }
 
+/// Transcribe a local recording into timestamped segments (local model only).
+#[tauri::command]
+pub async fn transcribe_structured(
+    wav_path: String,
+    store: tauri::State<'_, Store>,
+) -> Result<Vec<TranscriptSegment>, String> {
+    let trim = store.is_trim_silence_enabled();
+    let path = store
+        .resolve_local_model_path()
+        .ok_or_else(|| "Local model path not set".to_string())?;
+    tokio::task::spawn_blocking(move || transcribe_structured_local_sync(wav_path, path, trim))
+        .await
+        .map_err(|e| format!("Local transcription task failed: {}", e))?
+}
+
+/// Transcribe a local recording and render it as subtitles. `format` is `"srt"` or `"vtt"`.
+#[tauri::command]
+pub async fn export_subtitles(
+    wav_path: String,
+    format: String,
+    store: tauri::State<'_, Store>,
+) -> Result<String, String> {
+    let trim = store.is_trim_silence_enabled();
+    let path = store
+        .resolve_local_model_path()
+        .ok_or_else(|| "Local model path not set".to_string())?;
+    let segments = tokio::task::spawn_blocking(move || transcribe_structured_local_sync(wav_path, path, trim))
+        .await
+        .map_err(|e| format!("Local transcription task failed: {}", e))??;
+    Ok(match format.trim().to_lowercase().as_str() {
+        "vtt" => to_vtt(&segments),
+        _ => to_srt(&segments),
+    })
+}
+
 #[tauri::command]
 pub async fn transcribe(wav_path: String, store: tauri::State<'_, Store>) -> Result<String, String> {
+    let trim = store.is_trim_silence_enabled();
     let source = store.transcription_source();
     let endpoint = store.resolve_endpoint();
     let api_key = store.resolve_api_key();
     let local_model_path = store.resolve_local_model_path();
-    transcribe_impl(wav_path, source, endpoint, api_key, local_model_path).await
+    let openai = Some(OpenAiCredentials {
+        endpoint: store.resolve_openai_endpoint(),
+        api_key: store.resolve_openai_api_key(),
+        model: store.resolve_openai_model(),
+    });
+    // Bias decoding with both the vocabulary terms and the dictionary's boost phrases.
+    let prompt = match (store.vocabulary_prompt(), store.dictionary_prompt()) {
+        (Some(v), Some(d)) => Some(format!("{}, {}", v, d)),
+        (Some(v), None) => Some(v),
+        (None, Some(d)) => Some(d),
+        (None, None) => None,
+    };
+    let text =
+        transcribe_impl(wav_path, source, endpoint, api_key, local_model_path, openai, prompt, trim).await?;
+    // Deterministic fix-ups applied to the final text before paste (vocabulary then dictionary).
+    Ok(store.apply_replacements(&text))
 }
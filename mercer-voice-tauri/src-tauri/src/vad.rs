@@ -0,0 +1,139 @@
+//! Voice-activity detection that runs on captured PCM *before* Whisper, so silent or
+//! near-silent captures never reach the model (and never hit the Azure API). Short-time
+//! spectral analysis via a real FFT gives per-frame log-energy and spectral flatness; a
+//! frame counts as speech only when it rises above an adaptive noise floor *and* is tonal
+//! rather than broadband hiss. This kills "thank you"-style silence hallucinations at the
+//! source instead of filtering them out after the fact in `is_likely_hallucination`.
+
+use realfft::RealFftPlanner;
+
+/// 30 ms analysis frames at 16 kHz with 50% overlap.
+const FRAME_LEN: usize = 480;
+const HOP_LEN: usize = FRAME_LEN / 2;
+/// Speech must rise this many dB above the noise floor to open a segment.
+const ENERGY_MARGIN_DB: f32 = 6.0;
+/// Frames flatter (toward white noise) than this are rejected as non-tonal.
+const FLATNESS_THRESHOLD: f32 = 0.45;
+/// Minimum run of consecutive speech frames (~150 ms at the 15 ms hop) required to open a segment.
+const MIN_SPEECH_FRAMES: usize = 10;
+/// Frames of silence tolerated inside a segment before it closes (~90 ms at the 15 ms hop).
+const HANGOVER_FRAMES: usize = 6;
+/// Guard padding kept around the detected segment (~150 ms).
+const GUARD_SAMPLES: usize = 16_000 * 150 / 1000;
+
+/// Per-frame features.
+struct Frame {
+    energy_db: f32,
+    flatness: f32,
+}
+
+/// Analyse `samples` and return the trimmed `[start, end)` sample range that contains speech,
+/// or `None` when nothing qualifies so the caller can short-circuit without calling Whisper.
+/// `trim` carries the user's silence-trimming setting explicitly, per call.
+pub fn speech_bounds(samples: &[i16], trim: bool) -> Option<(usize, usize)> {
+    // When trimming is disabled, treat the whole capture as speech so nothing is dropped.
+    if !trim {
+        return Some((0, samples.len()));
+    }
+    if samples.len() < FRAME_LEN {
+        return None;
+    }
+
+    let mut planner = RealFftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(FRAME_LEN);
+    let hann: Vec<f32> = (0..FRAME_LEN)
+        .map(|n| {
+            let x = std::f32::consts::PI * n as f32 / (FRAME_LEN as f32 - 1.0);
+            x.sin().powi(2)
+        })
+        .collect();
+
+    let mut scratch = fft.make_input_vec();
+    let mut spectrum = fft.make_output_vec();
+    let mut frames: Vec<Frame> = Vec::new();
+
+    let mut pos = 0;
+    while pos + FRAME_LEN <= samples.len() {
+        for i in 0..FRAME_LEN {
+            scratch[i] = (samples[pos + i] as f32 / 32768.0) * hann[i];
+        }
+        if fft.process(&mut scratch, &mut spectrum).is_err() {
+            break;
+        }
+
+        let mut energy = 0.0f32;
+        let mut log_sum = 0.0f32;
+        let mut lin_sum = 0.0f32;
+        let n = spectrum.len().max(1);
+        for c in &spectrum {
+            let mag = c.norm();
+            energy += mag * mag;
+            log_sum += (mag + 1e-9).ln();
+            lin_sum += mag;
+        }
+        let geo_mean = (log_sum / n as f32).exp();
+        let arith_mean = lin_sum / n as f32;
+        let flatness = if arith_mean > 0.0 { geo_mean / arith_mean } else { 1.0 };
+        let energy_db = 10.0 * (energy + 1e-9).log10();
+
+        frames.push(Frame { energy_db, flatness });
+        pos += HOP_LEN;
+    }
+
+    if frames.is_empty() {
+        return None;
+    }
+
+    // Adaptive noise floor: running minimum of frame energy over the first few hundred ms.
+    let floor_frames = (frames.len() / 4).clamp(1, 16);
+    let noise_floor = frames[..floor_frames]
+        .iter()
+        .map(|f| f.energy_db)
+        .fold(f32::INFINITY, f32::min);
+
+    // Classify, requiring a minimum run and allowing hangover before closing.
+    let mut first = None;
+    let mut last = None;
+    let mut run = 0usize;
+    let mut gap = 0usize;
+    let mut open = false;
+    for (i, f) in frames.iter().enumerate() {
+        let is_speech = f.energy_db > noise_floor + ENERGY_MARGIN_DB && f.flatness < FLATNESS_THRESHOLD;
+        if is_speech {
+            run += 1;
+            gap = 0;
+            if !open && run >= MIN_SPEECH_FRAMES {
+                open = true;
+                if first.is_none() {
+                    first = Some(i + 1 - MIN_SPEECH_FRAMES);
+                }
+            }
+            if open {
+                last = Some(i);
+            }
+        } else {
+            run = 0;
+            if open {
+                gap += 1;
+                if gap > HANGOVER_FRAMES {
+                    open = false;
+                }
+            }
+        }
+    }
+
+    let (first, last) = (first?, last?);
+    let start = (first * HOP_LEN).saturating_sub(GUARD_SAMPLES);
+    let end = ((last * HOP_LEN) + FRAME_LEN + GUARD_SAMPLES).min(samples.len());
+    if end <= start {
+        return None;
+    }
+    Some((start, end))
+}
+
+/// Convenience wrapper: trim leading/trailing silence, returning `None` if no speech qualifies.
+/// `trim` carries the user's silence-trimming setting explicitly, per call.
+pub fn trim_silence(samples: &[i16], trim: bool) -> Option<Vec<i16>> {
+    let (start, end) = speech_bounds(samples, trim)?;
+    Some(samples[start..end].to_vec())
+}